@@ -0,0 +1,624 @@
+//! Checking usernames: single checks, streamed runs, retries and run control.
+
+use chrono::Utc;
+use futures::channel::mpsc;
+use futures::{future, stream, SinkExt, StreamExt};
+use reqwest::Proxy;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::Instant;
+
+use crate::backend::AvailabilityBackend;
+use crate::concurrency::{Concurrency, ConcurrencyGate, Signal};
+use crate::error::{CheckError, ErrorKind, InvalidReason};
+use crate::rate_limit::{self, RateBuckets, RateLimit, TokenBucket};
+use crate::rules::UsernameRules;
+use crate::types::{CheckResult, ResultStatus};
+
+/// How rate-limited (HTTP 429) requests are retried.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Total tries per username, including the first.
+    pub max_attempts: u32,
+    /// Backoff before the first retry; doubles on each further retry.
+    pub base_delay: Duration,
+    /// Ceiling for any single backoff. A `Retry-After` longer than this ends
+    /// the retries rather than being cut short.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Exponential backoff for the given 1-based attempt, with up to 50% jitter.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exp = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_delay);
+        let jitter = exp.mul_f64(fastrand::f64() * 0.5);
+        (exp + jitter).min(self.max_delay)
+    }
+}
+
+/// Parses a `Retry-After` header in either delay-seconds or HTTP-date form.
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let wait = date.signed_duration_since(chrono::Utc::now());
+    Some(wait.to_std().unwrap_or(Duration::ZERO))
+}
+
+enum Attempt {
+    Finished(ResultStatus),
+    RateLimited(Option<Duration>),
+}
+
+/// Whether `username` fits Hytale's default rules: 3–16 ASCII letters,
+/// digits or `_`. See [`UsernameRules`] for configurable rules.
+pub fn is_valid_username(username: &str) -> bool {
+    UsernameRules::hytale().is_valid(username)
+}
+
+/// Like [`is_valid_username`], but says what's wrong.
+pub fn validate_username(username: &str) -> Result<(), InvalidReason> {
+    UsernameRules::hytale().validate(username)
+}
+
+/// Checks one username, retrying on rate limits per `retry`.
+///
+/// Names that break the backend's [`UsernameRules`] are reported as
+/// [`ResultStatus::Invalid`] without a request.
+pub async fn check_single_username(
+    client: &reqwest::Client,
+    backend: &dyn AvailabilityBackend,
+    username: String,
+    retry: &RetryPolicy,
+) -> CheckResult {
+    if !backend.rules().is_valid(&username) {
+        return invalid_result(username);
+    }
+    check_paced(client, backend, username, retry, None, None).await
+}
+
+/// Sends and retries a check, taking a token from `proxy_bucket` before every
+/// attempt and from `global_bucket` before every retry; the first attempt's
+/// global token is taken at launch, in launch order.
+async fn check_paced(
+    client: &reqwest::Client,
+    backend: &dyn AvailabilityBackend,
+    username: String,
+    retry: &RetryPolicy,
+    global_bucket: Option<&TokenBucket>,
+    proxy_bucket: Option<&TokenBucket>,
+) -> CheckResult {
+    let max_attempts = retry.max_attempts.max(1);
+    let mut attempt = 1;
+
+    loop {
+        if attempt > 1 {
+            rate_limit::acquire(global_bucket).await;
+        }
+        rate_limit::acquire(proxy_bucket).await;
+        let started = Instant::now();
+        let outcome = send_request(client, backend, &username).await;
+        let latency = started.elapsed();
+
+        match outcome {
+            Attempt::Finished(mut status) => {
+                if let ResultStatus::Error(error) = &mut status {
+                    error.attempts = attempt;
+                }
+                return CheckResult {
+                    username,
+                    status,
+                    attempts: attempt,
+                    latency,
+                    proxy: None,
+                    checked_at: Utc::now(),
+                };
+            }
+            Attempt::RateLimited(retry_after) => {
+                let detail = match retry_after {
+                    _ if attempt >= max_attempts => {
+                        format!("Rate limited after {} attempts", attempt)
+                    }
+                    // Retrying sooner than the server allows would only be
+                    // refused again.
+                    Some(wait) if wait > retry.max_delay => format!(
+                        "Rate limited; the server asked to wait {}s, longer than the {}s limit",
+                        wait.as_secs(),
+                        retry.max_delay.as_secs()
+                    ),
+                    _ => {
+                        let backoff = retry.backoff(attempt);
+                        tokio::time::sleep(retry_after.map_or(backoff, |d| d.max(backoff))).await;
+                        attempt += 1;
+                        continue;
+                    }
+                };
+
+                let mut error = CheckError::new(ErrorKind::RateLimited, detail).with_status(429);
+                error.attempts = attempt;
+                return CheckResult {
+                    username,
+                    status: ResultStatus::Error(error),
+                    attempts: attempt,
+                    latency,
+                    proxy: None,
+                    checked_at: Utc::now(),
+                };
+            }
+        }
+    }
+}
+
+fn invalid_result(username: String) -> CheckResult {
+    CheckResult {
+        username,
+        status: ResultStatus::Invalid,
+        attempts: 0,
+        latency: Duration::ZERO,
+        proxy: None,
+        checked_at: Utc::now(),
+    }
+}
+
+async fn send_request(
+    client: &reqwest::Client,
+    backend: &dyn AvailabilityBackend,
+    username: &str,
+) -> Attempt {
+    match backend
+        .build_request(client, username)
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await
+    {
+        Ok(response) => {
+            if response.status() == 429 {
+                let retry_after = response
+                    .headers()
+                    .get(reqwest::header::RETRY_AFTER)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(parse_retry_after);
+                return Attempt::RateLimited(retry_after);
+            }
+
+            let status = response.status();
+            if !status.is_success() {
+                let kind = if status.is_server_error() {
+                    ErrorKind::ServerError
+                } else if status.is_client_error() {
+                    ErrorKind::ClientError
+                } else {
+                    ErrorKind::Other
+                };
+                return Attempt::Finished(ResultStatus::Error(
+                    CheckError::new(kind, status.to_string()).with_status(status.as_u16()),
+                ));
+            }
+
+            match response.json::<serde_json::Value>().await {
+                Ok(body) => Attempt::Finished(backend.interpret(&body)),
+                Err(e) => Attempt::Finished(ResultStatus::Error(
+                    CheckError::from_reqwest(&e).with_status(status.as_u16()),
+                )),
+            }
+        }
+        Err(e) => Attempt::Finished(ResultStatus::Error(CheckError::from_reqwest(&e))),
+    }
+}
+
+/// Maps a finished check onto the adaptive controller's view of it.
+fn congestion_signal(result: &CheckResult) -> Signal {
+    match &result.status {
+        _ if result.attempts > 1 => Signal::Congested,
+        ResultStatus::Error(e) if e.kind == ErrorKind::Timeout => Signal::Congested,
+        ResultStatus::Available | ResultStatus::Taken => Signal::Success(result.latency),
+        _ => Signal::Neutral,
+    }
+}
+
+/// Progress reported by [`check_usernames_stream`].
+#[derive(Debug, Clone)]
+pub enum CheckEvent {
+    /// A username finished checking.
+    Result(CheckResult),
+    /// The effective number of in-flight requests changed.
+    Concurrency(usize),
+    /// Every username was checked. Not sent for cancelled runs.
+    Done,
+}
+
+#[derive(Default)]
+struct RunControl {
+    cancelled: AtomicBool,
+    paused: AtomicBool,
+    /// Woken on resume and on cancel.
+    resumed: Notify,
+}
+
+/// Controls a run started by [`check_usernames_stream`].
+#[derive(Clone)]
+pub struct CancelHandle(Arc<RunControl>);
+
+impl CancelHandle {
+    /// Stops the run; no further events are sent.
+    pub fn cancel(&self) {
+        self.0.cancelled.store(true, Ordering::SeqCst);
+        self.0.resumed.notify_waiters();
+    }
+
+    /// Stops launching new requests; in-flight ones still finish and report.
+    pub fn pause(&self) {
+        self.0.paused.store(true, Ordering::SeqCst);
+    }
+
+    /// Continues launching requests after [`CancelHandle::pause`].
+    pub fn resume(&self) {
+        self.0.paused.store(false, Ordering::SeqCst);
+        self.0.resumed.notify_waiters();
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Resolves once [`CancelHandle::cancel`] has been called.
+    async fn cancelled(&self) {
+        loop {
+            let woken = self.0.resumed.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            woken.await;
+        }
+    }
+
+    async fn wait_while_paused(&self) {
+        loop {
+            let resumed = self.0.resumed.notified();
+            if !self.0.paused.load(Ordering::SeqCst) || self.is_cancelled() {
+                return;
+            }
+            resumed.await;
+        }
+    }
+}
+
+/// Checks `usernames` in the background and streams results as they finish.
+///
+/// `proxies` are full proxy URLs (see [`ProxyType::format_proxy`](crate::ProxyType::format_proxy))
+/// used round-robin; an empty list, or one where no entry parses, connects
+/// directly. `usernames` is pulled lazily as slots free up, so it can be a
+/// generator such as [`Pattern::valid_names`](crate::Pattern::valid_names).
+/// Must be called from within a Tokio runtime.
+pub fn check_usernames_stream<I>(
+    usernames: I,
+    proxies: Vec<String>,
+    rate: RateLimit,
+    concurrency: Concurrency,
+    retry: RetryPolicy,
+    backend: Arc<dyn AvailabilityBackend>,
+) -> (mpsc::Receiver<CheckEvent>, CancelHandle)
+where
+    I: IntoIterator<Item = String>,
+    I::IntoIter: Send + 'static,
+{
+    check_usernames_stream_with_buckets(
+        usernames,
+        proxies,
+        RateBuckets::new(rate),
+        concurrency,
+        retry,
+        backend,
+    )
+}
+
+/// Like [`check_usernames_stream`], but draws from `buckets`, so runs going at
+/// the same time can share their global and per-proxy caps.
+pub fn check_usernames_stream_with_buckets<I>(
+    usernames: I,
+    proxies: Vec<String>,
+    buckets: RateBuckets,
+    concurrency: Concurrency,
+    retry: RetryPolicy,
+    backend: Arc<dyn AvailabilityBackend>,
+) -> (mpsc::Receiver<CheckEvent>, CancelHandle)
+where
+    I: IntoIterator<Item = String>,
+    I::IntoIter: Send + 'static,
+{
+    let usernames = usernames.into_iter();
+    let (mut tx, rx) = mpsc::channel(100);
+    let control = CancelHandle(Arc::default());
+    let cancel_handle = control.clone();
+
+    tokio::spawn(async move {
+        let proxy_index = AtomicUsize::new(0);
+
+        // Each client is paired with a credential-free label of its proxy and
+        // that proxy's bucket.
+        let direct = || {
+            let label: Option<Arc<str>> = None;
+            (Arc::new(build_client(None)), label, buckets.for_proxy(""))
+        };
+        let clients: Vec<_> = if proxies.is_empty() {
+            vec![direct()]
+        } else {
+            let built: Vec<_> = proxies
+                .iter()
+                .filter_map(|proxy_url| {
+                    Proxy::all(proxy_url).ok().map(|proxy| {
+                        (
+                            Arc::new(build_client(Some(proxy))),
+                            Some(Arc::from(proxy_label(proxy_url))),
+                            buckets.for_proxy(proxy_url),
+                        )
+                    })
+                })
+                .collect();
+            if built.is_empty() {
+                vec![direct()]
+            } else {
+                built
+            }
+        };
+
+        let global_bucket = buckets.global();
+        let gate = ConcurrencyGate::new(concurrency);
+        let mut reported_limit = 0;
+        let launch_control = control.clone();
+        let still_running = control.clone();
+
+        // Launches wait out any pause, then for a concurrency slot and a
+        // global token, in order; each request then waits on its own proxy's
+        // bucket, and retries wait on both buckets again. Names the rules reject never reach the API, so they skip
+        // the slot and both buckets. Results are forwarded in completion order.
+        let launch_gate = Arc::clone(&gate);
+        let launch_backend = Arc::clone(&backend);
+        let launch_bucket = global_bucket.clone();
+        let results = stream::iter(usernames)
+            .then(move |username| {
+                let control = launch_control.clone();
+                let gate = Arc::clone(&launch_gate);
+                let global_bucket = launch_bucket.clone();
+                let is_valid = launch_backend.rules().is_valid(&username);
+                async move {
+                    control.wait_while_paused().await;
+                    if !is_valid {
+                        return (username, None);
+                    }
+                    let permit = gate.acquire().await;
+                    rate_limit::acquire(global_bucket.as_deref()).await;
+                    (username, Some(permit))
+                }
+            })
+            .take_while(move |_| future::ready(!still_running.is_cancelled()))
+            .map(|(username, permit)| {
+                let Some(permit) = permit else {
+                    return future::Either::Left(future::ready(invalid_result(username)));
+                };
+                let idx = proxy_index.fetch_add(1, Ordering::SeqCst) % clients.len();
+                let (client, proxy, proxy_bucket) = clients[idx].clone();
+                let global_bucket = global_bucket.clone();
+                let gate = Arc::clone(&gate);
+                let backend = Arc::clone(&backend);
+                future::Either::Right(async move {
+                    let mut result = check_paced(
+                        &client,
+                        backend.as_ref(),
+                        username,
+                        &retry,
+                        global_bucket.as_deref(),
+                        proxy_bucket.as_deref(),
+                    )
+                    .await;
+                    if let Some(proxy) = proxy.filter(|_| result.attempts > 0) {
+                        if let ResultStatus::Error(error) = &mut result.status {
+                            error.attribute_to_proxy(&proxy);
+                        }
+                        result.proxy = Some(proxy.to_string());
+                    }
+                    gate.record(congestion_signal(&result));
+                    drop(permit);
+                    result
+                })
+            })
+            .buffer_unordered(concurrency.max());
+        futures::pin_mut!(results);
+
+        // Cancelling drops the stream, which aborts in-flight requests and
+        // any rate-limit backoff they're sleeping through.
+        loop {
+            let result = tokio::select! {
+                result = results.next() => result,
+                () = control.cancelled() => return,
+            };
+            let Some(result) = result else {
+                break;
+            };
+            if tx.send(CheckEvent::Result(result)).await.is_err() {
+                return;
+            }
+            let limit = gate.limit();
+            if limit != reported_limit {
+                reported_limit = limit;
+                if tx.send(CheckEvent::Concurrency(limit)).await.is_err() {
+                    return;
+                }
+            }
+        }
+
+        // A cancelled run ends silently so its `Done` can't be mistaken for a
+        // later run finishing.
+        if !control.is_cancelled() {
+            let _ = tx.send(CheckEvent::Done).await;
+        }
+    });
+
+    (rx, cancel_handle)
+}
+
+/// Strips the scheme and any `user:pass@` credentials from a proxy URL.
+fn proxy_label(proxy_url: &str) -> String {
+    let without_scheme = proxy_url
+        .split_once("://")
+        .map_or(proxy_url, |(_, rest)| rest);
+    without_scheme
+        .rsplit_once('@')
+        .map_or(without_scheme, |(_, host)| host)
+        .to_string()
+}
+
+fn build_client(proxy: Option<Proxy>) -> reqwest::Client {
+    let mut builder = reqwest::Client::builder()
+        .timeout(Duration::from_secs(15))
+        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36");
+
+    if let Some(p) = proxy {
+        builder = builder.proxy(p);
+    }
+
+    builder.build().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{BackendConfig, JsonBackend};
+    use crate::test_support;
+
+    /// Checks one name against a loopback server answering `status` and `body`.
+    async fn check_against(status: u16, body: &'static str) -> ResultStatus {
+        let (url, _) = test_support::serve(status, body).await;
+        let backend = JsonBackend::new(BackendConfig {
+            base_url: url,
+            ..BackendConfig::default()
+        });
+        let client = reqwest::Client::new();
+        let retry = RetryPolicy::default();
+        check_single_username(&client, &backend, "dream".to_string(), &retry)
+            .await
+            .status
+    }
+
+    #[tokio::test]
+    async fn error_statuses_are_errors_even_with_an_answer() {
+        // A 5xx or 4xx body that happens to parse must never read as Taken.
+        for (status, kind) in [(500, ErrorKind::ServerError), (404, ErrorKind::ClientError)] {
+            match check_against(status, r#"{"available": false}"#).await {
+                ResultStatus::Error(error) => {
+                    assert_eq!(error.kind, kind);
+                    assert_eq!(error.http_status, Some(status));
+                }
+                other => panic!("{} gave {:?}", status, other),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn success_without_an_answer_is_unknown() {
+        let status = check_against(200, r#"{"status": "ok"}"#).await;
+        assert_eq!(status, ResultStatus::Unknown);
+        assert!(status.is_retryable());
+        assert!(!status.is_definitive());
+
+        assert_eq!(
+            check_against(200, r#"{"available": false}"#).await,
+            ResultStatus::Taken
+        );
+    }
+
+    #[tokio::test]
+    async fn unparseable_bodies_are_errors() {
+        match check_against(200, "<html>").await {
+            ResultStatus::Error(error) => assert_eq!(error.kind, ErrorKind::MalformedResponse),
+            other => panic!("gave {:?}", other),
+        }
+    }
+
+    /// Checks one name against a loopback server that always rate-limits,
+    /// asking for `retry_after` seconds; returns the error and request count.
+    async fn rate_limited(retry_after: &'static str, retry: RetryPolicy) -> (CheckError, usize) {
+        let (url, requests) = test_support::serve_with_headers(429, retry_after, "{}").await;
+        let backend = JsonBackend::new(BackendConfig {
+            base_url: url,
+            ..BackendConfig::default()
+        });
+        let client = reqwest::Client::new();
+        let result = check_single_username(&client, &backend, "dream".to_string(), &retry).await;
+        let ResultStatus::Error(error) = result.status else {
+            panic!("gave {:?}", result.status);
+        };
+        assert_eq!(error.kind, ErrorKind::RateLimited);
+        assert_eq!(error.attempts, result.attempts);
+        let count = requests.lock().unwrap().len();
+        (error, count)
+    }
+
+    #[tokio::test]
+    async fn retries_rate_limits_up_to_max_attempts() {
+        let retry = RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+            ..RetryPolicy::default()
+        };
+        let (error, requests) = rate_limited("Retry-After: 0\r\n", retry).await;
+        assert_eq!(requests, 3);
+        assert_eq!(error.detail, "Rate limited after 3 attempts");
+    }
+
+    #[tokio::test]
+    async fn gives_up_when_retry_after_exceeds_the_cap() {
+        let (error, requests) = rate_limited("Retry-After: 120\r\n", RetryPolicy::default()).await;
+        assert_eq!(requests, 1);
+        assert_eq!(error.attempts, 1);
+        assert_eq!(
+            error.detail,
+            "Rate limited; the server asked to wait 120s, longer than the 60s limit"
+        );
+    }
+
+    #[test]
+    fn parses_retry_after_seconds() {
+        assert_eq!(parse_retry_after(" 30 "), Some(Duration::from_secs(30)));
+        assert_eq!(parse_retry_after("soon"), None);
+    }
+
+    #[test]
+    fn parses_retry_after_dates() {
+        let later = (Utc::now() + chrono::Duration::seconds(120)).to_rfc2822();
+        let wait = parse_retry_after(&later).unwrap();
+        assert!(wait > Duration::from_secs(100) && wait <= Duration::from_secs(120));
+
+        let past = "Wed, 21 Oct 2015 07:28:00 GMT";
+        assert_eq!(parse_retry_after(past), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn backoff_doubles_within_the_ceiling() {
+        let retry = RetryPolicy {
+            max_attempts: 5,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(5),
+        };
+        let second = retry.backoff(2);
+        assert!(second >= Duration::from_secs(2) && second <= Duration::from_secs(3));
+        assert_eq!(retry.backoff(10), Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,223 @@
+//! Where availability answers come from and how responses are read.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::rules::UsernameRules;
+use crate::types::ResultStatus;
+
+/// A service that can answer "is this username available?".
+pub trait AvailabilityBackend: Send + Sync {
+    /// The request that asks about `username`.
+    fn build_request(&self, client: &reqwest::Client, username: &str) -> reqwest::RequestBuilder;
+
+    /// Reads a successful response body; anything without a clear answer is
+    /// `ResultStatus::Unknown`.
+    fn interpret(&self, body: &Value) -> ResultStatus;
+
+    /// Names breaking these rules are never sent.
+    fn rules(&self) -> &UsernameRules {
+        UsernameRules::hytale()
+    }
+}
+
+/// Endpoint and response mapping for a JSON availability API.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackendConfig {
+    /// Scheme and host, such as `https://api.hytl.tools`; a trailing `/` is ignored.
+    pub base_url: String,
+    /// Request path; `{username}` is replaced with the URL-encoded name.
+    pub path_template: String,
+    /// Dotted path to a boolean that is `true` when the name is free.
+    pub available_field: Option<String>,
+    /// Dotted path to a boolean that is `true` when the name is taken.
+    pub taken_field: Option<String>,
+}
+
+impl Default for BackendConfig {
+    fn default() -> Self {
+        Self::hytl_tools()
+    }
+}
+
+impl BackendConfig {
+    /// The public hytl.tools API, used by default.
+    pub fn hytl_tools() -> Self {
+        Self {
+            base_url: "https://api.hytl.tools".to_string(),
+            path_template: "/check/{username}".to_string(),
+            available_field: Some("available".to_string()),
+            taken_field: None,
+        }
+    }
+}
+
+/// [`AvailabilityBackend`] for JSON APIs described by a [`BackendConfig`].
+pub struct JsonBackend {
+    config: BackendConfig,
+    rules: UsernameRules,
+}
+
+impl JsonBackend {
+    /// A backend for `config` that checks names against Hytale's default rules.
+    pub fn new(config: BackendConfig) -> Self {
+        Self {
+            config,
+            rules: UsernameRules::default(),
+        }
+    }
+
+    /// Uses `rules` instead of the defaults to decide which names are sent.
+    pub fn with_rules(mut self, rules: UsernameRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    fn url_for(&self, username: &str) -> String {
+        let path = self
+            .config
+            .path_template
+            .replace("{username}", &urlencoding::encode(username));
+        format!("{}{}", self.config.base_url.trim_end_matches('/'), path)
+    }
+}
+
+impl Default for JsonBackend {
+    fn default() -> Self {
+        Self::new(BackendConfig::default())
+    }
+}
+
+impl AvailabilityBackend for JsonBackend {
+    fn build_request(&self, client: &reqwest::Client, username: &str) -> reqwest::RequestBuilder {
+        client.get(self.url_for(username))
+    }
+
+    fn interpret(&self, body: &Value) -> ResultStatus {
+        let flag = |path: &Option<String>| {
+            path.as_deref()
+                .and_then(|p| lookup(body, p))
+                .and_then(Value::as_bool)
+        };
+
+        match (
+            flag(&self.config.available_field),
+            flag(&self.config.taken_field),
+        ) {
+            (Some(true), _) | (None, Some(false)) => ResultStatus::Available,
+            (Some(false), _) | (None, Some(true)) => ResultStatus::Taken,
+            (None, None) => ResultStatus::Unknown,
+        }
+    }
+
+    fn rules(&self) -> &UsernameRules {
+        &self.rules
+    }
+}
+
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .filter(|key| !key.is_empty())
+        .try_fold(value, |current, key| current.get(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{check_single_username, RetryPolicy};
+    use crate::test_support;
+    use serde_json::json;
+
+    fn nested(base_url: &str) -> JsonBackend {
+        JsonBackend::new(BackendConfig {
+            base_url: base_url.to_string(),
+            path_template: "/v2/names/{username}/status".to_string(),
+            available_field: None,
+            taken_field: Some("data.name.taken".to_string()),
+        })
+    }
+
+    #[test]
+    fn fills_in_the_path_template() {
+        let backend = nested("http://127.0.0.1:8080/");
+        assert_eq!(
+            backend.url_for("dream"),
+            "http://127.0.0.1:8080/v2/names/dream/status"
+        );
+        assert_eq!(
+            backend.url_for("a b/c"),
+            "http://127.0.0.1:8080/v2/names/a%20b%2Fc/status"
+        );
+    }
+
+    #[test]
+    fn reads_dotted_fields() {
+        let backend = nested("http://localhost");
+        let body = |taken: Value| json!({ "data": { "name": { "taken": taken } } });
+        assert_eq!(backend.interpret(&body(json!(true))), ResultStatus::Taken);
+        assert_eq!(
+            backend.interpret(&body(json!(false))),
+            ResultStatus::Available
+        );
+        assert_eq!(
+            backend.interpret(&json!({ "data": { "taken": true } })),
+            ResultStatus::Unknown
+        );
+    }
+
+    #[test]
+    fn prefers_the_available_field() {
+        let backend = JsonBackend::new(BackendConfig {
+            taken_field: Some("taken".to_string()),
+            ..BackendConfig::default()
+        });
+        assert_eq!(
+            backend.interpret(&json!({ "available": true, "taken": true })),
+            ResultStatus::Available
+        );
+        assert_eq!(
+            backend.interpret(&json!({ "taken": true })),
+            ResultStatus::Taken
+        );
+    }
+
+    #[tokio::test]
+    async fn checks_against_a_local_endpoint() {
+        let client = reqwest::Client::new();
+        let retry = RetryPolicy::default();
+        for (body, expected) in [
+            (
+                r#"{"data": {"name": {"taken": false}}}"#,
+                ResultStatus::Available,
+            ),
+            (
+                r#"{"data": {"name": {"taken": true}}}"#,
+                ResultStatus::Taken,
+            ),
+        ] {
+            let (url, requests) = test_support::serve(200, body).await;
+            let result =
+                check_single_username(&client, &nested(&url), "Dream_1".to_string(), &retry).await;
+            assert_eq!(result.status, expected);
+
+            let requests = requests.lock().unwrap();
+            assert_eq!(requests[0].method, "GET");
+            assert_eq!(requests[0].path, "/v2/names/Dream_1/status");
+        }
+    }
+
+    #[test]
+    fn missing_or_non_boolean_answers_are_unknown() {
+        let backend = JsonBackend::default();
+        assert_eq!(backend.interpret(&json!({})), ResultStatus::Unknown);
+        assert_eq!(
+            backend.interpret(&json!({ "available": "yes" })),
+            ResultStatus::Unknown
+        );
+        assert_eq!(
+            backend.interpret(&json!({ "error": "not found" })),
+            ResultStatus::Unknown
+        );
+    }
+}
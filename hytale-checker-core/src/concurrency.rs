@@ -0,0 +1,216 @@
+//! How many requests a run keeps in flight, fixed or adaptive.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tokio::time::Instant;
+
+/// How many requests the checker keeps in flight.
+#[derive(Debug, Clone, Copy)]
+pub enum Concurrency {
+    /// Always keep this many requests in flight.
+    Fixed(usize),
+    /// AIMD: grows by roughly one slot per window of successes and halves
+    /// on 429s or timeouts, staying within `1..=max`.
+    Adaptive {
+        /// In-flight requests to start with.
+        initial: usize,
+        /// In-flight requests never go above this.
+        max: usize,
+    },
+}
+
+impl Concurrency {
+    /// Ceiling for adaptive runs that don't pick their own.
+    pub const ADAPTIVE_MAX: usize = 64;
+
+    /// Adaptive concurrency starting at `initial`, capped at
+    /// [`Concurrency::ADAPTIVE_MAX`].
+    pub fn adaptive(initial: usize) -> Self {
+        Concurrency::Adaptive {
+            initial,
+            max: Self::ADAPTIVE_MAX,
+        }
+    }
+
+    /// Upper bound on in-flight requests for this mode.
+    pub fn max(&self) -> usize {
+        match *self {
+            Concurrency::Fixed(n) => n.max(1),
+            Concurrency::Adaptive { initial, max } => max.max(initial).max(1),
+        }
+    }
+}
+
+/// Outcome of a single request as seen by the controller.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Signal {
+    Success(Duration),
+    Congested,
+    Neutral,
+}
+
+const DECREASE_COOLDOWN: Duration = Duration::from_secs(1);
+const LATENCY_TOLERANCE: f64 = 2.0;
+const LATENCY_SMOOTHING: f64 = 0.1;
+
+pub(crate) struct ConcurrencyGate {
+    adaptive: bool,
+    max: usize,
+    state: Mutex<GateState>,
+    notify: Notify,
+}
+
+struct GateState {
+    limit: f64,
+    in_flight: usize,
+    baseline_latency: Option<f64>,
+    last_decrease: Option<Instant>,
+}
+
+pub(crate) struct Permit {
+    gate: Arc<ConcurrencyGate>,
+}
+
+impl ConcurrencyGate {
+    pub fn new(concurrency: Concurrency) -> Arc<Self> {
+        let (adaptive, initial) = match concurrency {
+            Concurrency::Fixed(n) => (false, n),
+            Concurrency::Adaptive { initial, .. } => (true, initial),
+        };
+        let max = concurrency.max();
+        Arc::new(Self {
+            adaptive,
+            max,
+            state: Mutex::new(GateState {
+                limit: initial.clamp(1, max) as f64,
+                in_flight: 0,
+                baseline_latency: None,
+                last_decrease: None,
+            }),
+            notify: Notify::new(),
+        })
+    }
+
+    /// Current effective concurrency.
+    pub fn limit(&self) -> usize {
+        self.state.lock().unwrap().limit as usize
+    }
+
+    pub async fn acquire(self: &Arc<Self>) -> Permit {
+        loop {
+            let notified = self.notify.notified();
+            {
+                let mut state = self.state.lock().unwrap();
+                if state.in_flight < state.limit as usize {
+                    state.in_flight += 1;
+                    return Permit {
+                        gate: Arc::clone(self),
+                    };
+                }
+            }
+            notified.await;
+        }
+    }
+
+    pub fn record(&self, signal: Signal) {
+        if !self.adaptive {
+            return;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        match signal {
+            Signal::Success(latency) => {
+                let latency = latency.as_secs_f64();
+                let baseline = *state.baseline_latency.get_or_insert(latency);
+                if latency <= baseline * LATENCY_TOLERANCE {
+                    state.limit = (state.limit + 1.0 / state.limit).min(self.max as f64);
+                }
+                state.baseline_latency = Some(baseline + (latency - baseline) * LATENCY_SMOOTHING);
+            }
+            Signal::Congested => {
+                let now = Instant::now();
+                let cooling = state
+                    .last_decrease
+                    .is_some_and(|at| now.duration_since(at) < DECREASE_COOLDOWN);
+                if !cooling {
+                    state.limit = (state.limit / 2.0).floor().max(1.0);
+                    state.last_decrease = Some(now);
+                }
+            }
+            Signal::Neutral => {}
+        }
+        drop(state);
+        self.notify.notify_waiters();
+    }
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        self.gate.state.lock().unwrap().in_flight -= 1;
+        self.gate.notify.notify_waiters();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LATENCY: Duration = Duration::from_millis(100);
+
+    #[tokio::test(start_paused = true)]
+    async fn adaptive_limit_grows_by_about_one_per_window_of_successes() {
+        let gate = ConcurrencyGate::new(Concurrency::Adaptive { initial: 2, max: 3 });
+
+        // 2 + 1/2 + 1/2.5 = 2.9: a window of two successes isn't quite a slot.
+        gate.record(Signal::Success(LATENCY));
+        gate.record(Signal::Success(LATENCY));
+        assert_eq!(gate.limit(), 2);
+        gate.record(Signal::Success(LATENCY));
+        assert_eq!(gate.limit(), 3);
+
+        for _ in 0..10 {
+            gate.record(Signal::Success(LATENCY));
+        }
+        assert_eq!(gate.limit(), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn slow_successes_hold_the_limit() {
+        let gate = ConcurrencyGate::new(Concurrency::adaptive(2));
+        gate.record(Signal::Success(LATENCY));
+        let grown = gate.state.lock().unwrap().limit;
+
+        gate.record(Signal::Success(LATENCY * 3));
+        gate.record(Signal::Neutral);
+        assert_eq!(gate.state.lock().unwrap().limit, grown);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn congestion_halves_the_limit_once_per_cooldown() {
+        let gate = ConcurrencyGate::new(Concurrency::adaptive(8));
+
+        gate.record(Signal::Congested);
+        assert_eq!(gate.limit(), 4);
+        gate.record(Signal::Congested);
+        assert_eq!(gate.limit(), 4);
+
+        tokio::time::advance(DECREASE_COOLDOWN).await;
+        gate.record(Signal::Congested);
+        assert_eq!(gate.limit(), 2);
+
+        for _ in 0..3 {
+            tokio::time::advance(DECREASE_COOLDOWN).await;
+            gate.record(Signal::Congested);
+        }
+        assert_eq!(gate.limit(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn fixed_limit_ignores_signals() {
+        let gate = ConcurrencyGate::new(Concurrency::Fixed(4));
+        gate.record(Signal::Congested);
+        gate.record(Signal::Success(LATENCY));
+        assert_eq!(gate.limit(), 4);
+    }
+}
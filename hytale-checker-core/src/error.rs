@@ -0,0 +1,214 @@
+//! Structured reasons for failed checks and invalid usernames.
+
+use serde::{Deserialize, Serialize};
+use std::error::Error as _;
+use std::fmt;
+
+/// Broad cause of a failed check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// The request or response took too long.
+    Timeout,
+    /// The proxy couldn't be reached.
+    ProxyConnect,
+    /// The API couldn't be reached directly.
+    Connect,
+    /// The TLS handshake or certificate check failed.
+    Tls,
+    /// The API answered with a 4xx status other than 429.
+    ClientError,
+    /// The API answered with a 5xx status.
+    ServerError,
+    /// Every attempt was answered with 429.
+    RateLimited,
+    /// The body wasn't the JSON the backend expects.
+    MalformedResponse,
+    /// Anything else.
+    Other,
+}
+
+impl ErrorKind {
+    /// Every kind, in the order filters list them.
+    pub const ALL: [ErrorKind; 9] = [
+        ErrorKind::Timeout,
+        ErrorKind::ProxyConnect,
+        ErrorKind::Connect,
+        ErrorKind::Tls,
+        ErrorKind::ClientError,
+        ErrorKind::ServerError,
+        ErrorKind::RateLimited,
+        ErrorKind::MalformedResponse,
+        ErrorKind::Other,
+    ];
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::Timeout => write!(f, "Timeout"),
+            ErrorKind::ProxyConnect => write!(f, "Proxy"),
+            ErrorKind::Connect => write!(f, "Connection"),
+            ErrorKind::Tls => write!(f, "TLS"),
+            ErrorKind::ClientError => write!(f, "Client"),
+            ErrorKind::ServerError => write!(f, "Server"),
+            ErrorKind::RateLimited => write!(f, "Rate limited"),
+            ErrorKind::MalformedResponse => write!(f, "Bad response"),
+            ErrorKind::Other => write!(f, "Other"),
+        }
+    }
+}
+
+/// Why a check failed, with enough context to filter and retry on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckError {
+    /// Broad cause, for filtering.
+    pub kind: ErrorKind,
+    /// Status of the final response, when there was one.
+    pub http_status: Option<u16>,
+    /// Credential-free `host:port` of the proxy used, if any.
+    pub proxy: Option<String>,
+    /// Requests made before giving up, including the first.
+    pub attempts: u32,
+    /// The underlying error message.
+    pub detail: String,
+}
+
+impl CheckError {
+    /// An error of `kind` after one attempt, with no status or proxy.
+    pub fn new(kind: ErrorKind, detail: impl Into<String>) -> Self {
+        Self {
+            kind,
+            http_status: None,
+            proxy: None,
+            attempts: 1,
+            detail: detail.into(),
+        }
+    }
+
+    /// Records the HTTP status of the final response.
+    pub fn with_status(mut self, status: u16) -> Self {
+        self.http_status = Some(status);
+        self
+    }
+
+    /// Classifies a transport or body error from `reqwest`.
+    pub fn from_reqwest(error: &reqwest::Error) -> Self {
+        let kind = if error.is_timeout() {
+            ErrorKind::Timeout
+        } else if is_tls_error(error) {
+            ErrorKind::Tls
+        } else if error.is_connect() {
+            ErrorKind::Connect
+        } else if error.is_decode() || error.is_body() {
+            ErrorKind::MalformedResponse
+        } else {
+            ErrorKind::Other
+        };
+
+        let mut err = Self::new(kind, error.to_string());
+        err.http_status = error.status().map(|s| s.as_u16());
+        err
+    }
+
+    /// Records which proxy served the request; connection failures through a
+    /// proxy are reported as proxy failures.
+    pub fn attribute_to_proxy(&mut self, proxy: &str) {
+        if self.kind == ErrorKind::Connect {
+            self.kind = ErrorKind::ProxyConnect;
+        }
+        self.proxy = Some(proxy.to_string());
+    }
+}
+
+impl fmt::Display for CheckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+        if let Some(status) = self.http_status {
+            write!(f, " · HTTP {}", status)?;
+        }
+        if let Some(proxy) = &self.proxy {
+            write!(f, " · {}", proxy)?;
+        }
+        if self.attempts > 1 {
+            write!(f, " · {} attempts", self.attempts)?;
+        }
+        Ok(())
+    }
+}
+
+/// Why a username fails validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidReason {
+    /// Fewer characters than the rules allow.
+    TooShort {
+        /// Characters in the name.
+        len: usize,
+        /// Fewest characters allowed.
+        min: usize,
+    },
+    /// More characters than the rules allow.
+    TooLong {
+        /// Characters in the name.
+        len: usize,
+        /// Most characters allowed.
+        max: usize,
+    },
+    /// `position` counts characters from 1.
+    BadCharacter {
+        /// The offending character.
+        character: char,
+        /// Where it is, counting from 1.
+        position: usize,
+    },
+    /// Starts with a character the rules don't allow there.
+    BadStart {
+        /// The first character.
+        character: char,
+    },
+    /// Ends with a character the rules don't allow there.
+    BadEnd {
+        /// The last character.
+        character: char,
+    },
+    /// Contains a banned substring.
+    Banned {
+        /// The banned substring, as configured.
+        substring: String,
+    },
+}
+
+impl fmt::Display for InvalidReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidReason::TooShort { len, min } => {
+                write!(f, "too short ({} characters, minimum {})", len, min)
+            }
+            InvalidReason::TooLong { len, max } => {
+                write!(f, "too long ({} characters, maximum {})", len, max)
+            }
+            InvalidReason::BadCharacter {
+                character,
+                position,
+            } => write!(f, "bad character {:?} at position {}", character, position),
+            InvalidReason::BadStart { character } => {
+                write!(f, "can't start with {:?}", character)
+            }
+            InvalidReason::BadEnd { character } => write!(f, "can't end with {:?}", character),
+            InvalidReason::Banned { substring } => write!(f, "contains {:?}", substring),
+        }
+    }
+}
+
+/// reqwest doesn't flag TLS failures, so look for them in the source chain.
+fn is_tls_error(error: &reqwest::Error) -> bool {
+    let mut source = error.source();
+    while let Some(err) = source {
+        let message = err.to_string().to_ascii_lowercase();
+        if message.contains("tls") || message.contains("certificate") || message.contains("ssl") {
+            return true;
+        }
+        source = err.source();
+    }
+    false
+}
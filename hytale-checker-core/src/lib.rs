@@ -0,0 +1,63 @@
+//! Engine for checking Hytale username availability.
+//!
+//! [`check_usernames_stream`] runs a whole batch through rotating proxies with
+//! rate limiting, retries and bounded concurrency, and reports each
+//! [`CheckResult`] as a [`CheckEvent`] the moment it finishes.
+//! [`check_single_username`] is the one-shot building block underneath.
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use futures::StreamExt;
+//! use hytale_checker_core::{
+//!     check_usernames_stream, CheckEvent, Concurrency, JsonBackend, RateLimit, RetryPolicy,
+//! };
+//!
+//! # async fn run() {
+//! let (mut events, _handle) = check_usernames_stream(
+//!     vec!["notch".into(), "jeb_".into()],
+//!     Vec::new(),
+//!     RateLimit { global_rps: 5.0, per_proxy_rps: 0.0 },
+//!     Concurrency::Fixed(4),
+//!     RetryPolicy::default(),
+//!     Arc::new(JsonBackend::default()),
+//! );
+//!
+//! while let Some(event) = events.next().await {
+//!     if let CheckEvent::Result(result) = event {
+//!         println!("{}: {:?}", result.username, result.status);
+//!     }
+//! }
+//! # }
+//! ```
+
+#![warn(missing_docs)]
+
+pub mod api;
+pub mod backend;
+pub mod concurrency;
+pub mod error;
+pub mod mutate;
+pub mod ndjson;
+pub mod pattern;
+pub mod proxy;
+pub mod rate_limit;
+pub mod rules;
+pub mod score;
+#[cfg(test)]
+mod test_support;
+pub mod types;
+pub mod webhook;
+
+pub use api::*;
+pub use backend::*;
+pub use concurrency::*;
+pub use error::*;
+pub use mutate::{mutate, Mutated, Mutation};
+pub use ndjson::NdjsonWriter;
+pub use pattern::{Pattern, PatternError};
+pub use proxy::*;
+pub use rate_limit::*;
+pub use rules::{CharSet, UsernameRules};
+pub use score::{score, Score};
+pub use types::*;
+pub use webhook::*;
@@ -0,0 +1,274 @@
+//! Derives candidate names from a seed wordlist.
+//!
+//! Each [`Mutation`] is applied to the seed words on its own (mutations are
+//! not chained), and everything is filtered through a [`UsernameRules`] and
+//! deduplicated case-insensitively, so the output can go straight to
+//! [`check_usernames_stream`](crate::check_usernames_stream).
+
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::rules::UsernameRules;
+
+const LEET: [(char, char); 6] = [
+    ('a', '4'),
+    ('e', '3'),
+    ('i', '1'),
+    ('o', '0'),
+    ('s', '5'),
+    ('t', '7'),
+];
+
+/// A transform applied to every seed word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mutation {
+    /// `dream` → `dr3am`, `dre4m`, `dr34m`, ...: each substitutable letter on
+    /// its own, plus all of them at once.
+    Leetspeak,
+    /// `_dream`, `dream_` and `_dream_`.
+    Underscores,
+    /// `dream0` … `dream99`, `dream00` … `dream09` and `0dream` … `9dream`.
+    Digits,
+    /// `ddream`, `drream`, ...: one letter doubled.
+    Doubling,
+    /// `dre`, `drea`: the word cut short, down to three characters.
+    Truncation,
+    /// `dreams`, `boxes`, `puppies`.
+    Plural,
+    /// `dreamnotch`: every ordered pair of two different seed words.
+    Combine,
+}
+
+impl Mutation {
+    /// Every mutation, in the order they're applied.
+    pub const ALL: [Mutation; 7] = [
+        Mutation::Leetspeak,
+        Mutation::Underscores,
+        Mutation::Digits,
+        Mutation::Doubling,
+        Mutation::Truncation,
+        Mutation::Plural,
+        Mutation::Combine,
+    ];
+
+    fn apply(&self, word: &str, seeds: &[String], out: &mut Vec<String>) {
+        match self {
+            Mutation::Leetspeak => {
+                let swap = |c: char| LEET.iter().find(|(from, _)| *from == c).map(|(_, to)| *to);
+                for (i, c) in word.char_indices() {
+                    if let Some(to) = swap(c) {
+                        let mut variant = word.to_string();
+                        variant.replace_range(i..i + 1, &to.to_string());
+                        out.push(variant);
+                    }
+                }
+                out.push(word.chars().map(|c| swap(c).unwrap_or(c)).collect());
+            }
+            Mutation::Underscores => {
+                out.push(format!("_{}", word));
+                out.push(format!("{}_", word));
+                out.push(format!("_{}_", word));
+            }
+            Mutation::Digits => {
+                out.extend((0..100).map(|n| format!("{}{}", word, n)));
+                out.extend((0..10).map(|n| format!("{}0{}", word, n)));
+                out.extend((0..10).map(|n| format!("{}{}", n, word)));
+            }
+            Mutation::Doubling => {
+                for (i, c) in word.char_indices() {
+                    if c.is_ascii_alphabetic() {
+                        let mut variant = word.to_string();
+                        variant.insert(i, c);
+                        out.push(variant);
+                    }
+                }
+            }
+            Mutation::Truncation => {
+                out.extend(
+                    word.char_indices()
+                        .skip(3)
+                        .map(|(i, _)| word[..i].to_string()),
+                );
+            }
+            Mutation::Plural => out.push(pluralize(word)),
+            Mutation::Combine => {
+                for other in seeds {
+                    if other != word {
+                        out.push(format!("{}{}", word, other));
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Mutation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Mutation::Leetspeak => write!(f, "Leetspeak"),
+            Mutation::Underscores => write!(f, "Underscores"),
+            Mutation::Digits => write!(f, "Digits"),
+            Mutation::Doubling => write!(f, "Doubling"),
+            Mutation::Truncation => write!(f, "Truncation"),
+            Mutation::Plural => write!(f, "Plural"),
+            Mutation::Combine => write!(f, "Combine"),
+        }
+    }
+}
+
+impl std::str::FromStr for Mutation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Mutation::ALL
+            .into_iter()
+            .find(|m| m.to_string().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                let names: Vec<String> = Mutation::ALL
+                    .iter()
+                    .map(|m| m.to_string().to_ascii_lowercase())
+                    .collect();
+                format!("unknown mutation '{}' (expected {})", s, names.join(", "))
+            })
+    }
+}
+
+/// The output of [`mutate`].
+#[derive(Debug, Clone, Default)]
+pub struct Mutated {
+    /// Valid, unique names: the seeds first, then each mutation's additions.
+    pub names: Vec<String>,
+    /// How many of `names` are seed words.
+    pub seeds: usize,
+    /// How many new names each mutation contributed, in the order applied.
+    pub added: Vec<(Mutation, usize)>,
+    /// Whether generation stopped early at the limit.
+    pub truncated: bool,
+}
+
+/// Applies `mutations` to the seed words and collects at most `limit`
+/// case-insensitively unique names that pass `rules`. Seeds are lowercased
+/// and trimmed; blank lines are ignored.
+pub fn mutate(
+    seeds: &[String],
+    mutations: &[Mutation],
+    rules: &UsernameRules,
+    limit: usize,
+) -> Mutated {
+    let words: Vec<String> = seeds
+        .iter()
+        .map(|s| s.trim().to_ascii_lowercase())
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    let mut seen = HashSet::new();
+    let mut mutated = Mutated::default();
+    let mut push = |name: String, mutated: &mut Mutated| {
+        if mutated.names.len() >= limit {
+            mutated.truncated = true;
+            return false;
+        }
+        if rules.is_valid(&name) && seen.insert(name.to_ascii_lowercase()) {
+            mutated.names.push(name);
+        }
+        true
+    };
+
+    for word in &words {
+        if !push(word.clone(), &mut mutated) {
+            return mutated;
+        }
+    }
+    mutated.seeds = mutated.names.len();
+
+    let mut buffer = Vec::new();
+    for mutation in Mutation::ALL.into_iter().filter(|m| mutations.contains(m)) {
+        let before = mutated.names.len();
+        for word in &words {
+            buffer.clear();
+            mutation.apply(word, &words, &mut buffer);
+            for name in buffer.drain(..) {
+                if !push(name, &mut mutated) {
+                    mutated.added.push((mutation, mutated.names.len() - before));
+                    return mutated;
+                }
+            }
+        }
+        mutated.added.push((mutation, mutated.names.len() - before));
+    }
+    mutated
+}
+
+fn pluralize(word: &str) -> String {
+    let is_vowel = |c: char| "aeiou".contains(c);
+    if let Some(stem) = word.strip_suffix('y')
+        && stem.chars().last().is_some_and(|c| !is_vowel(c))
+    {
+        format!("{}ies", stem)
+    } else if ["s", "x", "z", "ch", "sh"]
+        .iter()
+        .any(|end| word.ends_with(end))
+    {
+        format!("{}es", word)
+    } else {
+        format!("{}s", word)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seeds(words: &[&str]) -> Vec<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn keeps_seeds_first_and_dedupes() {
+        let mutated = mutate(
+            &seeds(&["Dream", "dream", " ", "ab"]),
+            &[Mutation::Underscores],
+            &UsernameRules::default(),
+            usize::MAX,
+        );
+        assert_eq!(mutated.seeds, 1);
+        assert_eq!(
+            mutated.names,
+            ["dream", "_dream", "dream_", "_dream_", "_ab", "ab_", "_ab_"]
+        );
+        assert_eq!(mutated.added, [(Mutation::Underscores, 6)]);
+        assert!(!mutated.truncated);
+    }
+
+    #[test]
+    fn applies_mutations_separately() {
+        let mutated = mutate(
+            &seeds(&["box", "sky"]),
+            &[Mutation::Plural, Mutation::Combine],
+            &UsernameRules::default(),
+            usize::MAX,
+        );
+        assert_eq!(
+            mutated.names,
+            ["box", "sky", "boxes", "skies", "boxsky", "skybox"]
+        );
+    }
+
+    #[test]
+    fn stops_at_the_limit() {
+        let mutated = mutate(
+            &seeds(&["dream"]),
+            &[Mutation::Digits],
+            &UsernameRules::default(),
+            5,
+        );
+        assert_eq!(mutated.names.len(), 5);
+        assert!(mutated.truncated);
+    }
+
+    #[test]
+    fn parses_mutation_names() {
+        assert_eq!("LEETSPEAK".parse::<Mutation>(), Ok(Mutation::Leetspeak));
+        assert!("shout".parse::<Mutation>().is_err());
+    }
+}
@@ -0,0 +1,97 @@
+//! Newline-delimited JSON output for results.
+
+use chrono::SecondsFormat;
+use serde::Serialize;
+use std::io::{self, Write};
+
+use crate::error::{CheckError, ErrorKind};
+use crate::types::{CheckResult, ResultStatus};
+
+/// The flat, stable shape of one result in NDJSON output.
+#[derive(Debug, Serialize)]
+pub struct ResultRecord<'a> {
+    /// The name as checked.
+    pub username: &'a str,
+    /// `available`, `taken`, `error`, `unknown` or `invalid`.
+    pub status: &'static str,
+    /// Present only for errors.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorRecord<'a>>,
+    /// Credential-free `host:port` of the proxy used, if any.
+    pub proxy: Option<&'a str>,
+    /// Round-trip time of the final request.
+    pub latency_ms: u64,
+    /// Requests made, including retries.
+    pub attempts: u32,
+    /// When the check finished, as RFC 3339 UTC with milliseconds.
+    pub timestamp: String,
+}
+
+/// Why a check failed, as written in NDJSON output.
+#[derive(Debug, Serialize)]
+pub struct ErrorRecord<'a> {
+    /// Broad cause, in snake case.
+    pub kind: ErrorKind,
+    /// Status of the final response, when there was one.
+    pub http_status: Option<u16>,
+    /// One-line description, as shown in the window.
+    pub summary: String,
+    /// The underlying error message.
+    pub detail: &'a str,
+}
+
+impl<'a> From<&'a CheckResult> for ResultRecord<'a> {
+    fn from(result: &'a CheckResult) -> Self {
+        let error = match &result.status {
+            ResultStatus::Error(e) => Some(ErrorRecord::from(e)),
+            _ => None,
+        };
+
+        Self {
+            username: &result.username,
+            status: result.status.label(),
+            error,
+            proxy: result.proxy.as_deref(),
+            latency_ms: result.latency.as_millis() as u64,
+            attempts: result.attempts,
+            timestamp: result
+                .checked_at
+                .to_rfc3339_opts(SecondsFormat::Millis, true),
+        }
+    }
+}
+
+impl<'a> From<&'a CheckError> for ErrorRecord<'a> {
+    fn from(error: &'a CheckError) -> Self {
+        Self {
+            kind: error.kind,
+            http_status: error.http_status,
+            summary: error.to_string(),
+            detail: &error.detail,
+        }
+    }
+}
+
+/// Serializes a result as a single JSON line, without the trailing newline.
+pub fn to_line(result: &CheckResult) -> String {
+    serde_json::to_string(&ResultRecord::from(result)).expect("result records always serialize")
+}
+
+/// Writes one JSON object per result and flushes after each, so readers
+/// see results as soon as they are produced.
+pub struct NdjsonWriter<W: Write> {
+    inner: W,
+}
+
+impl<W: Write> NdjsonWriter<W> {
+    /// Writes records to `inner`.
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    /// Writes one result as a line and flushes.
+    pub fn write(&mut self, result: &CheckResult) -> io::Result<()> {
+        writeln!(self.inner, "{}", to_line(result))?;
+        self.inner.flush()
+    }
+}
@@ -0,0 +1,709 @@
+//! Expands username patterns into candidate names.
+//!
+//! A pattern is a sequence of:
+//!
+//! - literal characters: anything but whitespace and `[](){}<>|\`
+//! - character classes: `[a-z]`, `[aeiou0-9]`, and the shorthands `\c`
+//!   (consonant), `\v` (vowel), `\l` (letter) and `\d` (digit), which also
+//!   work inside brackets; in brackets a lone `c` or `v` also means a
+//!   consonant or vowel, so `[cv]` is any letter, while `[a-c]` is a range
+//! - a repeat count after a character or class: `{3}` or `{2,4}`
+//! - alternatives: `(the|mr|)`; an empty alternative makes the group optional
+//! - number ranges: `<1-99>`, or `<01-99>` to zero-pad to the width of the start
+//!
+//! For example `[c][v][c]<0-9>` or `\c\v\c<0-9>` yields `bab0` … `zuz9`, and `(x|)[a-z]{3}`
+//! yields every three-letter name with and without an `x` prefix.
+//!
+//! Expansions are filtered by a [`UsernameRules`], so characters outside its
+//! allowed set may appear in a pattern but never in the output.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::rules::UsernameRules;
+
+const CONSONANTS: &str = "bcdfghjklmnpqrstvwxyz";
+const VOWELS: &str = "aeiou";
+const LETTERS: &str = "abcdefghijklmnopqrstuvwxyz";
+const DIGITS: &str = "0123456789";
+
+/// Keeps counting cheap; far longer than any name a service would accept.
+const MAX_REPEAT: u32 = 64;
+const SYNTAX_CHARS: &str = "[](){}<>|\\";
+/// Patterns the length-based count can overcount are scanned instead, up to
+/// this many expansions; beyond it the count is an upper bound.
+const COUNT_SCAN_LIMIT: u128 = 100_000;
+
+/// Why a pattern couldn't be parsed; `position` is a character offset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternError {
+    /// Character offset of the problem, from 0.
+    pub position: usize,
+    /// What's wrong, without the position.
+    pub message: String,
+}
+
+impl PatternError {
+    fn new(position: usize, message: impl Into<String>) -> Self {
+        Self {
+            position,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (at character {})", self.message, self.position + 1)
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+#[derive(Debug, Clone)]
+enum Segment {
+    /// Exactly one of these strings.
+    Choice(Vec<String>),
+    /// Between `min` and `max` characters, each drawn from `chars`.
+    Repeat {
+        chars: Vec<char>,
+        min: u32,
+        max: u32,
+    },
+    /// A decimal number, zero-padded to `width`.
+    Number { start: u64, end: u64, width: usize },
+}
+
+impl Segment {
+    fn count(&self) -> Option<u128> {
+        match self {
+            Segment::Choice(options) => Some(options.len() as u128),
+            Segment::Repeat { chars, min, max } => (*min..=*max).try_fold(0u128, |total, len| {
+                total.checked_add((chars.len() as u128).checked_pow(len)?)
+            }),
+            Segment::Number { start, end, .. } => Some((end - start) as u128 + 1),
+        }
+    }
+
+    /// Whether outputs differ in length, so neighbouring segments can trade
+    /// characters and produce the same name twice.
+    fn varies(&self) -> bool {
+        match self {
+            Segment::Choice(options) => options
+                .iter()
+                .any(|o| o.chars().count() != options[0].chars().count()),
+            Segment::Repeat { min, max, .. } => min != max,
+            Segment::Number { start, end, width } => {
+                number_len(*start, *width) != number_len(*end, *width)
+            }
+        }
+    }
+
+    /// Whether `piece` is one of this segment's outputs.
+    fn matches(&self, piece: &[char]) -> bool {
+        match self {
+            Segment::Choice(options) => options.iter().any(|o| o.chars().eq(piece.iter().copied())),
+            Segment::Repeat { chars, min, max } => {
+                (*min..=*max).contains(&(piece.len() as u32))
+                    && piece.iter().all(|c| chars.contains(c))
+            }
+            Segment::Number { start, end, width } => {
+                let digits: String = piece.iter().collect();
+                match digits.parse::<u64>() {
+                    Ok(n) if digits.bytes().all(|b| b.is_ascii_digit()) => {
+                        (*start..=*end).contains(&n) && number_len(n, *width) == piece.len()
+                    }
+                    _ => false,
+                }
+            }
+        }
+    }
+
+    /// How many of this segment's outputs have each length, counting only
+    /// outputs made of characters `rules` allows.
+    fn lengths(&self, rules: &UsernameRules) -> BTreeMap<usize, u128> {
+        let allowed = |c: char| rules.allowed_chars.contains(c);
+        let mut lengths = BTreeMap::new();
+        match self {
+            Segment::Choice(options) => {
+                for option in options.iter().filter(|o| o.chars().all(allowed)) {
+                    *lengths.entry(option.chars().count()).or_default() += 1;
+                }
+            }
+            Segment::Repeat { chars, min, max } => {
+                let usable = chars.iter().filter(|c| allowed(**c)).count() as u128;
+                for len in *min..=*max {
+                    if len as usize > rules.max_length {
+                        break;
+                    }
+                    let count = usable.saturating_pow(len);
+                    *lengths.entry(len as usize).or_default() += count;
+                }
+            }
+            Segment::Number { .. } if !DIGITS.chars().any(allowed) => {}
+            Segment::Number { start, end, width } => {
+                for digits in 1..=20u32 {
+                    let low = if digits == 1 {
+                        0
+                    } else {
+                        10u64.pow(digits - 1)
+                    };
+                    let high = 10u64.checked_pow(digits).map_or(u64::MAX, |n| n - 1);
+                    let (low, high) = ((*start).max(low), (*end).min(high));
+                    if low <= high {
+                        let len = (digits as usize).max(*width);
+                        *lengths.entry(len).or_default() += (high - low) as u128 + 1;
+                    }
+                }
+            }
+        }
+        lengths
+    }
+
+    fn write(&self, index: u128, out: &mut String) {
+        match self {
+            Segment::Choice(options) => out.push_str(&options[index as usize]),
+            Segment::Repeat { chars, min, max } => {
+                let base = chars.len() as u128;
+                let mut index = index;
+                for len in *min..=*max {
+                    let count = base.pow(len);
+                    if index < count {
+                        let start = out.len();
+                        for _ in 0..len {
+                            out.insert(start, chars[(index % base) as usize]);
+                            index /= base;
+                        }
+                        return;
+                    }
+                    index -= count;
+                }
+            }
+            Segment::Number { start, width, .. } => {
+                let n = *start as u128 + index;
+                out.push_str(&format!("{:0width$}", n, width = *width));
+            }
+        }
+    }
+}
+
+/// A parsed pattern. Candidates are produced lazily, so even patterns with
+/// billions of expansions cost nothing until iterated.
+///
+/// Letters are lowercased while parsing, so `[Dd]ream` yields `dream` once.
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    segments: Vec<Segment>,
+    total: u128,
+    /// More than one segment varies in length, so a name like `abc` can come
+    /// out of `(a|)(a|)bc` twice.
+    ambiguous: bool,
+}
+
+impl Pattern {
+    /// Parses `source`, ignoring surrounding whitespace.
+    pub fn parse(source: &str) -> Result<Self, PatternError> {
+        let segments = Parser::new(source.trim()).parse()?;
+        let total = segments
+            .iter()
+            .try_fold(1u128, |total, segment| total.checked_mul(segment.count()?))
+            .ok_or_else(|| PatternError::new(0, "Pattern expands to too many names"))?;
+
+        let ambiguous = segments.iter().filter(|s| s.varies()).count() > 1;
+
+        Ok(Self {
+            segments,
+            total,
+            ambiguous,
+        })
+    }
+
+    /// Every expansion, including ones that aren't valid usernames.
+    pub fn count(&self) -> u128 {
+        self.total
+    }
+
+    /// Distinct expansions that pass `rules`.
+    ///
+    /// The count is worked out from segment lengths, which is exact for the
+    /// length and character rules. Patterns that can produce a name more than
+    /// one way, and rules with leading, trailing or banned-substring
+    /// constraints, are counted by scanning the expansions instead; past
+    /// 100,000 expansions that's too slow and the length-based count is
+    /// returned as an upper bound. See [`Pattern::is_count_exact`].
+    pub fn valid_count(&self, rules: &UsernameRules) -> u128 {
+        if self.needs_scan(rules) && self.total <= COUNT_SCAN_LIMIT {
+            return self.clone().valid_names(rules.clone()).count() as u128;
+        }
+
+        let mut lengths = BTreeMap::from([(0usize, 1u128)]);
+        for segment in &self.segments {
+            let seg_lengths = segment.lengths(rules);
+            let mut next = BTreeMap::new();
+            for (len, count) in &lengths {
+                for (seg_len, seg_count) in &seg_lengths {
+                    let total = len + seg_len;
+                    if total <= rules.max_length {
+                        let entry: &mut u128 = next.entry(total).or_default();
+                        *entry = entry.saturating_add(count.saturating_mul(*seg_count));
+                    }
+                }
+            }
+            lengths = next;
+        }
+
+        lengths
+            .range(rules.min_length..=rules.max_length)
+            .fold(0u128, |total, (_, count)| total.saturating_add(*count))
+    }
+
+    /// Every expansion in order, the last part of the pattern varying fastest.
+    pub fn candidates(self) -> Candidates {
+        Candidates {
+            pattern: self,
+            next: 0,
+        }
+    }
+
+    /// Whether [`Pattern::valid_count`] is exact under `rules` rather than an
+    /// upper bound.
+    pub fn is_count_exact(&self, rules: &UsernameRules) -> bool {
+        !self.needs_scan(rules) || self.total <= COUNT_SCAN_LIMIT
+    }
+
+    /// Whether counting by length could overcount: a name can come out more
+    /// than one way, or `rules` reject names for more than their length and
+    /// characters.
+    fn needs_scan(&self, rules: &UsernameRules) -> bool {
+        self.ambiguous || !rules.is_length_and_charset_only()
+    }
+
+    /// The distinct expansions that pass `rules`.
+    pub fn valid_names(
+        self,
+        rules: UsernameRules,
+    ) -> impl Iterator<Item = String> + Send + 'static {
+        let ambiguous = self.ambiguous;
+        let mut candidates = self.candidates();
+        std::iter::from_fn(move || loop {
+            let (name, lengths) = candidates.next_expansion()?;
+            if rules.is_valid(&name)
+                && (!ambiguous || candidates.pattern.is_first_split(&name, &lengths))
+            {
+                return Some(name);
+            }
+        })
+    }
+
+    /// Whether `lengths` is the first way, trying shorter pieces first, to
+    /// split `name` into the segments' outputs. Every name has exactly one
+    /// first split, so keeping only those expansions drops repeats without
+    /// remembering what was already produced.
+    fn is_first_split(&self, name: &str, lengths: &[usize]) -> bool {
+        let chars: Vec<char> = name.chars().collect();
+        let mut dead = vec![false; (self.segments.len() + 1) * (chars.len() + 1)];
+        let mut split = Vec::with_capacity(self.segments.len());
+        self.split_from(0, 0, &chars, &mut split, &mut dead) && split == lengths
+    }
+
+    fn split_from(
+        &self,
+        segment: usize,
+        pos: usize,
+        name: &[char],
+        split: &mut Vec<usize>,
+        dead: &mut [bool],
+    ) -> bool {
+        let Some(current) = self.segments.get(segment) else {
+            return pos == name.len();
+        };
+        // A (segment, position) pair that failed once fails every time.
+        let key = segment * (name.len() + 1) + pos;
+        if dead[key] {
+            return false;
+        }
+
+        for len in 0..=name.len() - pos {
+            if current.matches(&name[pos..pos + len]) {
+                split.push(len);
+                if self.split_from(segment + 1, pos + len, name, split, dead) {
+                    return true;
+                }
+                split.pop();
+            }
+        }
+        dead[key] = true;
+        false
+    }
+}
+
+/// Iterator over a [`Pattern`]'s expansions.
+pub struct Candidates {
+    pattern: Pattern,
+    next: u128,
+}
+
+impl Iterator for Candidates {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        self.next_expansion().map(|(name, _)| name)
+    }
+}
+
+impl Candidates {
+    /// The next expansion and how many characters each segment wrote.
+    fn next_expansion(&mut self) -> Option<(String, Vec<usize>)> {
+        if self.next >= self.pattern.total {
+            return None;
+        }
+
+        // Decode the index as a mixed-radix number, one digit per segment.
+        let mut indices = Vec::with_capacity(self.pattern.segments.len());
+        let mut rest = self.next;
+        for segment in self.pattern.segments.iter().rev() {
+            let count = segment.count().unwrap_or(1);
+            indices.push(rest % count);
+            rest /= count;
+        }
+
+        let mut name = String::new();
+        let mut lengths = Vec::with_capacity(indices.len());
+        for (segment, index) in self.pattern.segments.iter().zip(indices.into_iter().rev()) {
+            let before = name.chars().count();
+            segment.write(index, &mut name);
+            lengths.push(name.chars().count() - before);
+        }
+        self.next += 1;
+        Some((name, lengths))
+    }
+}
+
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::iter::Enumerate<std::str::Chars<'a>>>,
+    segments: Vec<Segment>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> Self {
+        Self {
+            chars: source.chars().enumerate().peekable(),
+            segments: Vec::new(),
+        }
+    }
+
+    fn parse(mut self) -> Result<Vec<Segment>, PatternError> {
+        while let Some((pos, c)) = self.chars.next() {
+            let segment = match c {
+                '[' => Segment::Choice(to_options(self.class(pos)?)),
+                '\\' => Segment::Choice(to_options(self.shorthand(pos)?.chars().collect())),
+                '(' => Segment::Choice(self.alternatives(pos)?),
+                '<' => self.number(pos)?,
+                '{' => {
+                    let (min, max) = self.repeat(pos)?;
+                    let chars = match self.segments.pop() {
+                        Some(Segment::Choice(options))
+                            if options.iter().all(|o| o.chars().count() == 1) =>
+                        {
+                            options.iter().filter_map(|o| o.chars().next()).collect()
+                        }
+                        _ => {
+                            return Err(PatternError::new(
+                                pos,
+                                "A repeat count must follow a character or class",
+                            ));
+                        }
+                    };
+                    Segment::Repeat { chars, min, max }
+                }
+                c if is_name_char(c) => Segment::Choice(vec![c.to_ascii_lowercase().to_string()]),
+                c => return Err(PatternError::new(pos, format!("Unexpected '{}'", c))),
+            };
+            self.segments.push(segment);
+        }
+
+        if self.segments.is_empty() {
+            return Err(PatternError::new(0, "Pattern is empty"));
+        }
+        Ok(self.segments)
+    }
+
+    fn shorthand(&mut self, start: usize) -> Result<&'static str, PatternError> {
+        match self.chars.next() {
+            Some((_, 'c')) => Ok(CONSONANTS),
+            Some((_, 'v')) => Ok(VOWELS),
+            Some((_, 'l')) => Ok(LETTERS),
+            Some((_, 'd')) => Ok(DIGITS),
+            Some((pos, c)) => Err(PatternError::new(pos, format!("Unknown class '\\{}'", c))),
+            None => Err(PatternError::new(start, "Pattern ends after '\\'")),
+        }
+    }
+
+    fn class(&mut self, start: usize) -> Result<Vec<char>, PatternError> {
+        let mut chars: Vec<char> = Vec::new();
+        let mut add = |c: char| {
+            let c = c.to_ascii_lowercase();
+            if !chars.contains(&c) {
+                chars.push(c);
+            }
+        };
+
+        loop {
+            match self.chars.next() {
+                Some((_, ']')) => break,
+                Some((pos, '\\')) => self.shorthand(pos)?.chars().for_each(&mut add),
+                Some((pos, c)) if is_name_char(c) => {
+                    if self.chars.next_if(|(_, next)| *next == '-').is_some() {
+                        let (end_pos, end) = self
+                            .chars
+                            .next()
+                            .ok_or_else(|| PatternError::new(pos, "Unclosed '['"))?;
+                        if !is_name_char(end) || end < c {
+                            return Err(PatternError::new(end_pos, "Invalid range"));
+                        }
+                        (c..=end).filter(|c| is_name_char(*c)).for_each(&mut add);
+                    } else {
+                        match c.to_ascii_lowercase() {
+                            'c' => CONSONANTS.chars().for_each(&mut add),
+                            'v' => VOWELS.chars().for_each(&mut add),
+                            _ => add(c),
+                        }
+                    }
+                }
+                Some((pos, c)) => {
+                    return Err(PatternError::new(
+                        pos,
+                        format!("Unexpected '{}' in class", c),
+                    ));
+                }
+                None => return Err(PatternError::new(start, "Unclosed '['")),
+            }
+        }
+
+        if chars.is_empty() {
+            return Err(PatternError::new(start, "Empty class"));
+        }
+        Ok(chars)
+    }
+
+    fn alternatives(&mut self, start: usize) -> Result<Vec<String>, PatternError> {
+        let mut options = vec![String::new()];
+        loop {
+            match self.chars.next() {
+                Some((_, ')')) => break,
+                Some((_, '|')) => options.push(String::new()),
+                Some((_, c)) if is_name_char(c) => {
+                    options.last_mut().unwrap().push(c.to_ascii_lowercase())
+                }
+                Some((pos, c)) => {
+                    return Err(PatternError::new(
+                        pos,
+                        format!("Unexpected '{}' in group", c),
+                    ));
+                }
+                None => return Err(PatternError::new(start, "Unclosed '('")),
+            }
+        }
+
+        let mut unique = Vec::with_capacity(options.len());
+        for option in options {
+            if !unique.contains(&option) {
+                unique.push(option);
+            }
+        }
+        Ok(unique)
+    }
+
+    fn number(&mut self, start: usize) -> Result<Segment, PatternError> {
+        let low = self.digits();
+        if self.chars.next_if(|(_, c)| *c == '-').is_none() {
+            return Err(PatternError::new(start, "Expected a range like <1-99>"));
+        }
+        let high = self.digits();
+        if self.chars.next_if(|(_, c)| *c == '>').is_none() {
+            return Err(PatternError::new(start, "Unclosed '<'"));
+        }
+
+        let (Ok(low_value), Ok(high_value)) = (low.parse::<u64>(), high.parse::<u64>()) else {
+            return Err(PatternError::new(start, "Expected a range like <1-99>"));
+        };
+        if low_value > high_value {
+            return Err(PatternError::new(start, "Range start is after its end"));
+        }
+
+        let width = if low.len() > 1 && low.starts_with('0') {
+            low.len()
+        } else {
+            0
+        };
+        Ok(Segment::Number {
+            start: low_value,
+            end: high_value,
+            width,
+        })
+    }
+
+    fn repeat(&mut self, start: usize) -> Result<(u32, u32), PatternError> {
+        let min = self.digits();
+        let max = if self.chars.next_if(|(_, c)| *c == ',').is_some() {
+            self.digits()
+        } else {
+            min.clone()
+        };
+        if self.chars.next_if(|(_, c)| *c == '}').is_none() {
+            return Err(PatternError::new(start, "Unclosed '{'"));
+        }
+
+        match (min.parse::<u32>(), max.parse::<u32>()) {
+            (Ok(min), Ok(max)) if min <= max && max <= MAX_REPEAT => Ok((min, max)),
+            (Ok(_), Ok(max)) if max > MAX_REPEAT => Err(PatternError::new(
+                start,
+                format!("Repeat counts above {} aren't supported", MAX_REPEAT),
+            )),
+            _ => Err(PatternError::new(
+                start,
+                "Expected a count like {3} or {2,4}",
+            )),
+        }
+    }
+
+    fn digits(&mut self) -> String {
+        let mut digits = String::new();
+        while let Some((_, c)) = self.chars.next_if(|(_, c)| c.is_ascii_digit()) {
+            digits.push(c);
+        }
+        digits
+    }
+}
+
+fn is_name_char(c: char) -> bool {
+    !c.is_whitespace() && !c.is_control() && !SYNTAX_CHARS.contains(c)
+}
+
+/// Characters `n` takes when zero-padded to `width`.
+fn number_len(n: u64, width: usize) -> usize {
+    n.to_string().len().max(width)
+}
+
+fn to_options(chars: Vec<char>) -> Vec<String> {
+    chars.into_iter().map(String::from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::CharSet;
+
+    fn names(source: &str) -> Vec<String> {
+        Pattern::parse(source)
+            .unwrap()
+            .valid_names(UsernameRules::default())
+            .collect()
+    }
+
+    fn valid_count(source: &str) -> u128 {
+        Pattern::parse(source)
+            .unwrap()
+            .valid_count(&UsernameRules::default())
+    }
+
+    #[test]
+    fn counts_classes_and_ranges() {
+        let pattern = Pattern::parse(r"\c\v\c<0-9>").unwrap();
+        assert_eq!(pattern.count(), 21 * 5 * 21 * 10);
+        assert_eq!(valid_count(r"\c\v\c<0-9>"), 22050);
+        assert_eq!(names(r"\c\v\c<0-9>").first().unwrap(), "bab0");
+    }
+
+    #[test]
+    fn expands_alternatives_and_repeats() {
+        assert_eq!(names("(mr|the)cat"), ["mrcat", "thecat"]);
+        assert_eq!(names("ab[xy]{2}"), ["abxx", "abxy", "abyx", "abyy"]);
+        assert_eq!(names("(x|)abc"), ["xabc", "abc"]);
+    }
+
+    #[test]
+    fn pads_numbers_to_the_start_width() {
+        assert_eq!(names("ab<08-10>"), ["ab08", "ab09", "ab10"]);
+        assert_eq!(names("ab<8-10>"), ["ab8", "ab9", "ab10"]);
+    }
+
+    #[test]
+    fn filters_by_rules() {
+        // One and two letters are too short for the default rules.
+        assert_eq!(valid_count("[a-c]{1,3}"), 27);
+        assert_eq!(names("a-b(c|)"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn counts_edge_and_banned_rules_exactly_when_small() {
+        let rules = UsernameRules {
+            disallowed_leading: CharSet::parse("0-9").unwrap(),
+            banned_substrings: vec!["aa".to_string()],
+            ..Default::default()
+        };
+        let pattern = Pattern::parse("[a0][ab][ab]").unwrap();
+        assert!(pattern.is_count_exact(&rules));
+        // aab, aaa and every name starting with 0 are out.
+        assert_eq!(pattern.valid_count(&rules), 2);
+
+        let large = Pattern::parse("[a-z]{4}[0-9]").unwrap();
+        assert!(!large.is_count_exact(&rules));
+        assert_eq!(large.valid_count(&rules), 26u128.pow(4) * 10);
+    }
+
+    #[test]
+    fn c_and_v_in_classes_are_consonants_and_vowels() {
+        assert_eq!(valid_count("[c][v][c]"), 21 * 5 * 21);
+        assert_eq!(valid_count("[cv][cv][cv]"), 26 * 26 * 26);
+        assert_eq!(names("ab[V]"), ["aba", "abe", "abi", "abo", "abu"]);
+        // Range endpoints and letters outside brackets stay literal.
+        assert_eq!(names("ab[b-c]"), ["abb", "abc"]);
+        assert_eq!(names("cv[0]"), ["cv0"]);
+    }
+
+    #[test]
+    fn lowercases_letters() {
+        assert_eq!(names("[Dd]ream"), ["dream"]);
+        assert_eq!(names("(Mr|mr)Cat"), ["mrcat"]);
+        assert_eq!(valid_count("[Dd]ream"), 1);
+    }
+
+    #[test]
+    fn produces_each_name_once() {
+        // `abc` comes out of either group; `bc` is too short.
+        assert_eq!(names("(a|)(a|)bc"), ["aabc", "abc"]);
+        assert_eq!(valid_count("(a|)(a|)bc"), 2);
+
+        let all = names("[a-c]{1,3}[a-c]{1,3}");
+        let unique: std::collections::HashSet<_> = all.iter().collect();
+        assert_eq!(all.len(), unique.len());
+        assert_eq!(valid_count("[a-c]{1,3}[a-c]{1,3}"), all.len() as u128);
+        assert!(Pattern::parse("[a-c]{1,3}[a-c]{1,3}")
+            .unwrap()
+            .is_count_exact(&UsernameRules::default()));
+    }
+
+    #[test]
+    fn large_ambiguous_counts_are_upper_bounds() {
+        let pattern = Pattern::parse("[a-z]{1,3}[a-z]{1,3}").unwrap();
+        assert!(!pattern.is_count_exact(&UsernameRules::default()));
+        assert!(pattern.valid_count(&UsernameRules::default()) > 0);
+    }
+
+    #[test]
+    fn reports_error_positions() {
+        let error = |source: &str| Pattern::parse(source).unwrap_err();
+        assert_eq!(error("ab[c").position, 2);
+        assert_eq!(error("(ab|cd").position, 0);
+        assert_eq!(error("a{3").position, 1);
+        assert_eq!(error("ab)").position, 2);
+        assert_eq!(error("[z-a]").position, 3);
+        assert_eq!(error(r"\q").position, 1);
+        assert_eq!(error("<9-1>").message, "Range start is after its end");
+        assert_eq!(
+            error("{3}").to_string(),
+            "A repeat count must follow a character or class (at character 1)"
+        );
+    }
+}
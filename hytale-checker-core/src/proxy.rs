@@ -1,10 +1,20 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+//! Proxy types and turning proxy list entries into URLs.
+
+use serde::{Deserialize, Serialize};
+
+/// Proxy protocol applied to `host:port` entries without a scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum ProxyType {
     #[default]
+    /// Connect directly.
     None,
+    /// `http://` proxies.
     Http,
+    /// `https://` proxies.
     Https,
+    /// `socks4://` proxies.
     Socks4,
+    /// `socks5://` proxies.
     Socks5,
 }
 
@@ -21,6 +31,7 @@ impl std::fmt::Display for ProxyType {
 }
 
 impl ProxyType {
+    /// Every type, in the order pickers list them.
     pub const ALL: [ProxyType; 5] = [
         ProxyType::None,
         ProxyType::Http,
@@ -29,6 +40,7 @@ impl ProxyType {
         ProxyType::Socks5,
     ];
 
+    /// The URL scheme for this type, including `://`; empty for `None`.
     pub fn prefix(&self) -> &'static str {
         match self {
             ProxyType::None => "",
@@ -39,6 +51,7 @@ impl ProxyType {
         }
     }
 
+    /// Turns a list entry into a proxy URL, keeping any scheme it already has.
     pub fn format_proxy(&self, proxy: &str) -> String {
         if proxy.contains("://") {
             proxy.to_string()
@@ -47,3 +60,21 @@ impl ProxyType {
         }
     }
 }
+
+impl std::str::FromStr for ProxyType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "none" => Ok(ProxyType::None),
+            "http" => Ok(ProxyType::Http),
+            "https" => Ok(ProxyType::Https),
+            "socks4" => Ok(ProxyType::Socks4),
+            "socks5" => Ok(ProxyType::Socks5),
+            _ => Err(format!(
+                "unknown proxy type '{}' (expected none, http, https, socks4 or socks5)",
+                s
+            )),
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! Requests-per-second caps and the token buckets that enforce them.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Requests-per-second caps; `0.0` disables a cap.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RateLimit {
+    /// Cap across the whole run.
+    pub global_rps: f64,
+    /// Cap applied separately to each proxy (or the direct connection).
+    pub per_proxy_rps: f64,
+}
+
+/// The token buckets behind a [`RateLimit`]. Clones share their buckets, so
+/// runs started with clones of the same `RateBuckets` (see
+/// [`check_usernames_stream_with_buckets`](crate::check_usernames_stream_with_buckets))
+/// stay under the caps together, including on a proxy they both use.
+#[derive(Clone, Default)]
+pub struct RateBuckets {
+    rate: RateLimit,
+    global: Option<Arc<TokenBucket>>,
+    /// Keyed by proxy URL; the direct connection is `""`.
+    per_proxy: Arc<Mutex<HashMap<String, Option<Arc<TokenBucket>>>>>,
+}
+
+impl RateBuckets {
+    /// Fresh buckets for `rate`.
+    pub fn new(rate: RateLimit) -> Self {
+        Self {
+            rate,
+            global: TokenBucket::new(rate.global_rps).map(Arc::new),
+            per_proxy: Arc::default(),
+        }
+    }
+
+    /// The caps these buckets enforce.
+    pub fn rate(&self) -> RateLimit {
+        self.rate
+    }
+
+    pub(crate) fn global(&self) -> Option<Arc<TokenBucket>> {
+        self.global.clone()
+    }
+
+    /// The bucket for `proxy_url`, created on first use.
+    pub(crate) fn for_proxy(&self, proxy_url: &str) -> Option<Arc<TokenBucket>> {
+        self.per_proxy
+            .lock()
+            .unwrap()
+            .entry(proxy_url.to_string())
+            .or_insert_with(|| TokenBucket::new(self.rate.per_proxy_rps).map(Arc::new))
+            .clone()
+    }
+}
+
+pub(crate) struct TokenBucket {
+    rate: f64,
+    capacity: f64,
+    state: Mutex<BucketState>,
+}
+
+struct BucketState {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Returns `None` for a non-positive rate, meaning "unlimited".
+    pub fn new(rate: f64) -> Option<Self> {
+        if !rate.is_finite() || rate <= 0.0 {
+            return None;
+        }
+        Some(Self {
+            rate,
+            capacity: rate.max(1.0),
+            state: Mutex::new(BucketState {
+                tokens: 1.0,
+                last_refill: Instant::now(),
+            }),
+        })
+    }
+
+    pub async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().unwrap();
+                let now = Instant::now();
+                let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+                state.tokens = (state.tokens + elapsed * self.rate).min(self.capacity);
+                state.last_refill = now;
+
+                if state.tokens >= 1.0 {
+                    state.tokens -= 1.0;
+                    return;
+                }
+                Duration::from_secs_f64((1.0 - state.tokens) / self.rate)
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+pub(crate) async fn acquire(bucket: Option<&TokenBucket>) {
+    if let Some(bucket) = bucket {
+        bucket.acquire().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_positive_rates_are_unlimited() {
+        assert!(TokenBucket::new(0.0).is_none());
+        assert!(TokenBucket::new(-1.0).is_none());
+        assert!(TokenBucket::new(f64::NAN).is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn bucket_refills_at_its_rate() {
+        let bucket = TokenBucket::new(2.0).unwrap();
+        let start = Instant::now();
+
+        bucket.acquire().await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+        bucket.acquire().await;
+        assert_eq!(start.elapsed(), Duration::from_millis(500));
+        bucket.acquire().await;
+        assert_eq!(start.elapsed(), Duration::from_secs(1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn idle_time_banks_at_most_one_second_of_tokens() {
+        let bucket = TokenBucket::new(2.0).unwrap();
+        tokio::time::advance(Duration::from_secs(10)).await;
+        let start = Instant::now();
+
+        bucket.acquire().await;
+        bucket.acquire().await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+        bucket.acquire().await;
+        assert_eq!(start.elapsed(), Duration::from_millis(500));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn slow_rates_still_allow_one_request() {
+        let bucket = TokenBucket::new(0.5).unwrap();
+        let start = Instant::now();
+
+        bucket.acquire().await;
+        assert_eq!(start.elapsed(), Duration::ZERO);
+        bucket.acquire().await;
+        assert_eq!(start.elapsed(), Duration::from_secs(2));
+    }
+}
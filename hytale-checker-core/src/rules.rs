@@ -0,0 +1,242 @@
+//! Configurable rules for what makes a username acceptable.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::LazyLock;
+
+use crate::error::InvalidReason;
+
+static HYTALE_RULES: LazyLock<UsernameRules> = LazyLock::new(UsernameRules::default);
+
+/// What makes a username acceptable. The defaults are Hytale's current
+/// rules: 3–16 ASCII letters, digits or `_`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UsernameRules {
+    /// Fewest characters allowed.
+    pub min_length: usize,
+    /// Most characters allowed.
+    pub max_length: usize,
+    /// Characters a name may contain.
+    pub allowed_chars: CharSet,
+    /// Characters a name may not start with.
+    pub disallowed_leading: CharSet,
+    /// Characters a name may not end with.
+    pub disallowed_trailing: CharSet,
+    /// Substrings that may not appear anywhere, ignoring case.
+    pub banned_substrings: Vec<String>,
+}
+
+impl Default for UsernameRules {
+    fn default() -> Self {
+        Self {
+            min_length: 3,
+            max_length: 16,
+            allowed_chars: CharSet::parse("a-zA-Z0-9_").unwrap(),
+            disallowed_leading: CharSet::default(),
+            disallowed_trailing: CharSet::default(),
+            banned_substrings: Vec::new(),
+        }
+    }
+}
+
+impl UsernameRules {
+    /// Shared instance of the default rules.
+    pub fn hytale() -> &'static UsernameRules {
+        &HYTALE_RULES
+    }
+
+    /// Whether only the length and allowed characters are restricted, so
+    /// whether a name passes doesn't depend on where each character sits.
+    pub(crate) fn is_length_and_charset_only(&self) -> bool {
+        self.disallowed_leading.is_empty()
+            && self.disallowed_trailing.is_empty()
+            && self.banned_substrings.iter().all(|b| b.is_empty())
+    }
+
+    /// Whether `username` passes every rule.
+    pub fn is_valid(&self, username: &str) -> bool {
+        self.validate(username).is_ok()
+    }
+
+    /// Checks characters, then length, then the first and last character,
+    /// then banned substrings, and reports the first problem found.
+    pub fn validate(&self, username: &str) -> Result<(), InvalidReason> {
+        if let Some((position, character)) = username
+            .chars()
+            .enumerate()
+            .find(|(_, c)| !self.allowed_chars.contains(*c))
+        {
+            return Err(InvalidReason::BadCharacter {
+                character,
+                position: position + 1,
+            });
+        }
+
+        let len = username.chars().count();
+        if len < self.min_length {
+            return Err(InvalidReason::TooShort {
+                len,
+                min: self.min_length,
+            });
+        }
+        if len > self.max_length {
+            return Err(InvalidReason::TooLong {
+                len,
+                max: self.max_length,
+            });
+        }
+
+        if let Some(first) = username.chars().next()
+            && self.disallowed_leading.contains(first)
+        {
+            return Err(InvalidReason::BadStart { character: first });
+        }
+        if let Some(last) = username.chars().last()
+            && self.disallowed_trailing.contains(last)
+        {
+            return Err(InvalidReason::BadEnd { character: last });
+        }
+
+        let lower = username.to_lowercase();
+        if let Some(banned) = self
+            .banned_substrings
+            .iter()
+            .find(|banned| !banned.is_empty() && lower.contains(&banned.to_lowercase()))
+        {
+            return Err(InvalidReason::Banned {
+                substring: banned.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// A set of characters written like a regex class body: `a-zA-Z0-9_`. A `-`
+/// at the start or end is literal.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct CharSet {
+    spec: String,
+    ranges: Vec<(char, char)>,
+}
+
+impl CharSet {
+    /// Parses a spec such as `a-z0-9_`; fails on a backwards range.
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let chars: Vec<char> = spec.chars().collect();
+        let mut ranges = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+            if i + 2 < chars.len() && chars[i + 1] == '-' {
+                let (start, end) = (chars[i], chars[i + 2]);
+                if start > end {
+                    return Err(format!("invalid range {}-{}", start, end));
+                }
+                ranges.push((start, end));
+                i += 3;
+            } else {
+                ranges.push((chars[i], chars[i]));
+                i += 1;
+            }
+        }
+
+        Ok(Self {
+            spec: spec.to_string(),
+            ranges,
+        })
+    }
+
+    /// Whether the set holds no characters.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Whether `c` is in the set.
+    pub fn contains(&self, c: char) -> bool {
+        self.ranges
+            .iter()
+            .any(|(start, end)| (*start..=*end).contains(&c))
+    }
+}
+
+impl fmt::Display for CharSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.spec)
+    }
+}
+
+impl TryFrom<String> for CharSet {
+    type Error = String;
+
+    fn try_from(spec: String) -> Result<Self, Self::Error> {
+        CharSet::parse(&spec)
+    }
+}
+
+impl From<CharSet> for String {
+    fn from(set: CharSet) -> Self {
+        set.spec
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_rules_match_hytale() {
+        let rules = UsernameRules::hytale();
+        assert!(rules.is_valid("Dream_99"));
+        assert_eq!(
+            rules.validate("ab"),
+            Err(InvalidReason::TooShort { len: 2, min: 3 })
+        );
+        assert_eq!(
+            rules.validate("a".repeat(17).as_str()),
+            Err(InvalidReason::TooLong { len: 17, max: 16 })
+        );
+        assert_eq!(
+            rules.validate("ab-cd"),
+            Err(InvalidReason::BadCharacter {
+                character: '-',
+                position: 3
+            })
+        );
+    }
+
+    #[test]
+    fn checks_ends_and_banned_substrings() {
+        let rules = UsernameRules {
+            disallowed_leading: CharSet::parse("0-9_").unwrap(),
+            disallowed_trailing: CharSet::parse("_").unwrap(),
+            banned_substrings: vec!["Admin".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            rules.validate("1abc"),
+            Err(InvalidReason::BadStart { character: '1' })
+        );
+        assert_eq!(
+            rules.validate("abc_"),
+            Err(InvalidReason::BadEnd { character: '_' })
+        );
+        assert_eq!(
+            rules.validate("theADMIN"),
+            Err(InvalidReason::Banned {
+                substring: "Admin".to_string()
+            })
+        );
+        assert!(rules.is_valid("a_b1"));
+    }
+
+    #[test]
+    fn parses_char_sets() {
+        let set = CharSet::parse("a-c_-").unwrap();
+        assert!(set.contains('b'));
+        assert!(set.contains('_'));
+        assert!(set.contains('-'));
+        assert!(!set.contains('d'));
+        assert!(CharSet::parse("z-a").is_err());
+    }
+}
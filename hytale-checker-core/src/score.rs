@@ -0,0 +1,169 @@
+//! Rates how desirable a username is, so a long list of available names can
+//! be sorted best-first.
+
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+/// Common short English words; a name built from them scores higher.
+const WORDS: &str = include_str!("words.txt");
+
+/// A 0–100 rating and the parts it's made of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Score {
+    /// Sum of the parts below.
+    pub total: u8,
+    /// Shorter is better; up to 30.
+    pub length: u8,
+    /// Consonants and vowels alternate without long runs; up to 25.
+    pub pronounceable: u8,
+    /// The name is, or is made of, dictionary words; up to 25.
+    pub dictionary: u8,
+    /// Few digits and underscores; up to 10.
+    pub clean: u8,
+    /// No character directly repeated; up to 10.
+    pub distinct: u8,
+}
+
+fn dictionary() -> &'static HashSet<&'static str> {
+    static DICTIONARY: OnceLock<HashSet<&'static str>> = OnceLock::new();
+    DICTIONARY.get_or_init(|| WORDS.lines().filter(|w| !w.is_empty()).collect())
+}
+
+/// Rates `username`, ignoring case.
+pub fn score(username: &str) -> Score {
+    let name = username.to_ascii_lowercase();
+    let chars: Vec<char> = name.chars().collect();
+    let letters: String = chars.iter().filter(|c| c.is_ascii_alphabetic()).collect();
+
+    let length = 30usize.saturating_sub(3 * chars.len().saturating_sub(3)) as u8;
+
+    let symbols = chars.iter().filter(|c| !c.is_ascii_alphabetic()).count();
+    let clean = 10usize.saturating_sub(3 * symbols) as u8;
+
+    let repeats = chars.windows(2).filter(|pair| pair[0] == pair[1]).count();
+    let distinct = 10usize.saturating_sub(4 * repeats) as u8;
+
+    let score = Score {
+        total: 0,
+        length,
+        pronounceable: pronounceability(&letters),
+        dictionary: dictionary_score(&letters),
+        clean,
+        distinct,
+    };
+    Score {
+        total: score.length + score.pronounceable + score.dictionary + score.clean + score.distinct,
+        ..score
+    }
+}
+
+/// Full marks when no more than two consonants or two vowels ever appear in a
+/// row; each letter past that costs its share. Names without vowels get none.
+fn pronounceability(letters: &str) -> u8 {
+    let is_vowel = |c: char| "aeiouy".contains(c);
+    if !letters.chars().any(is_vowel) {
+        return 0;
+    }
+
+    let mut excess = 0;
+    let mut run = 0;
+    let mut previous = None;
+    for c in letters.chars() {
+        if previous == Some(is_vowel(c)) {
+            run += 1;
+        } else {
+            run = 1;
+        }
+        if run > 2 {
+            excess += 1;
+        }
+        previous = Some(is_vowel(c));
+    }
+
+    let ratio = 1.0 - excess as f32 / letters.len() as f32;
+    (25.0 * ratio).round() as u8
+}
+
+fn dictionary_score(letters: &str) -> u8 {
+    let words = dictionary();
+    if words.contains(letters) {
+        return 25;
+    }
+
+    if (1..letters.len()).any(|i| words.contains(&letters[..i]) && words.contains(&letters[i..])) {
+        return 18;
+    }
+
+    let contains_word = (0..letters.len())
+        .any(|start| (start + 4..=letters.len()).any(|end| words.contains(&letters[start..end])));
+    if contains_word {
+        8
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shorter_names_score_higher_on_length() {
+        assert_eq!(score("abc").length, 30);
+        assert_eq!(score("abcd").length, 27);
+        assert_eq!(score("abcdefgh").length, 15);
+        assert_eq!(score("abcdefghijklm").length, 0);
+        assert_eq!(score("abcdefghijklmnop").length, 0);
+    }
+
+    #[test]
+    fn long_consonant_or_vowel_runs_cost_pronounceability() {
+        assert_eq!(score("banana").pronounceable, 25);
+        assert_eq!(score("tryst").pronounceable, 25);
+        // Three of the eight letters extend a run past two: 25 * 5/8.
+        assert_eq!(score("strength").pronounceable, 16);
+        assert_eq!(score("bcdfg").pronounceable, 0);
+        assert_eq!(score("x_1").pronounceable, 0);
+    }
+
+    #[test]
+    fn dictionary_words_and_pairs_score_highest() {
+        assert_eq!(score("dragon").dictionary, 25);
+        assert_eq!(score("sunstar").dictionary, 18);
+        assert_eq!(score("qdragonq").dictionary, 8);
+        assert_eq!(score("qsunq").dictionary, 0);
+        assert_eq!(score("zzxq").dictionary, 0);
+        // Digits and underscores are ignored when looking words up.
+        assert_eq!(score("dragon_1").dictionary, 25);
+    }
+
+    #[test]
+    fn digits_and_underscores_cost_cleanliness() {
+        assert_eq!(score("abcd").clean, 10);
+        assert_eq!(score("abc1").clean, 7);
+        assert_eq!(score("a_b1").clean, 4);
+        assert_eq!(score("a_1_2").clean, 0);
+    }
+
+    #[test]
+    fn repeated_characters_cost_distinctness() {
+        assert_eq!(score("abab").distinct, 10);
+        assert_eq!(score("aab").distinct, 6);
+        assert_eq!(score("aabb").distinct, 2);
+        assert_eq!(score("aaabb").distinct, 0);
+    }
+
+    #[test]
+    fn total_adds_up_the_parts_ignoring_case() {
+        let sun = score("Sun");
+        assert_eq!(sun, score("sun"));
+        assert_eq!(sun.total, 100);
+
+        let s = score("xx_9qz");
+        assert_eq!(
+            s.total,
+            s.length + s.pronounceable + s.dictionary + s.clean + s.distinct
+        );
+        assert!(s.total < score("dragon").total);
+    }
+}
@@ -0,0 +1,94 @@
+//! A loopback HTTP server for tests that need a real endpoint.
+
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// One request the server received.
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub body: String,
+}
+
+/// Serves every request on 127.0.0.1 with `status` and `body`, recording what
+/// came in. Returns the base URL (`http://127.0.0.1:port`) and the requests.
+pub async fn serve(status: u16, body: &'static str) -> (String, Arc<Mutex<Vec<Request>>>) {
+    serve_with_headers(status, "", body).await
+}
+
+/// [`serve`], adding `headers` (each ending in `\r\n`) to every response.
+pub async fn serve_with_headers(
+    status: u16,
+    headers: &'static str,
+    body: &'static str,
+) -> (String, Arc<Mutex<Vec<Request>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+
+    let seen = Arc::clone(&requests);
+    tokio::spawn(async move {
+        loop {
+            let Ok((mut socket, _)) = listener.accept().await else {
+                return;
+            };
+            let seen = Arc::clone(&seen);
+            tokio::spawn(async move {
+                let Some(request) = read_request(&mut socket).await else {
+                    return;
+                };
+                seen.lock().unwrap().push(request);
+                let response = format!(
+                    "HTTP/1.1 {} Test\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n{}\r\n{}",
+                    status,
+                    body.len(),
+                    headers,
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+                let _ = socket.shutdown().await;
+            });
+        }
+    });
+
+    (url, requests)
+}
+
+async fn read_request(socket: &mut tokio::net::TcpStream) -> Option<Request> {
+    let mut data = Vec::new();
+    let mut buf = [0u8; 4096];
+    let header_end = loop {
+        let n = socket.read(&mut buf).await.ok()?;
+        if n == 0 {
+            return None;
+        }
+        data.extend_from_slice(&buf[..n]);
+        if let Some(end) = data.windows(4).position(|w| w == b"\r\n\r\n") {
+            break end + 4;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&data[..header_end]).to_string();
+    let content_length = head
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+    while data.len() < header_end + content_length {
+        let n = socket.read(&mut buf).await.ok()?;
+        if n == 0 {
+            break;
+        }
+        data.extend_from_slice(&buf[..n]);
+    }
+
+    let mut request_line = head.lines().next()?.split_whitespace();
+    Some(Request {
+        method: request_line.next()?.to_string(),
+        path: request_line.next()?.to_string(),
+        body: String::from_utf8_lossy(&data[header_end..]).to_string(),
+    })
+}
@@ -0,0 +1,103 @@
+//! Results of checks and running totals.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::error::CheckError;
+
+/// The outcome of checking one username.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckResult {
+    /// The name as checked.
+    pub username: String,
+    /// What the check found.
+    pub status: ResultStatus,
+    /// Requests made, including retries; `0` for names rejected locally.
+    pub attempts: u32,
+    /// Round-trip time of the final request.
+    pub latency: Duration,
+    /// Credential-free `host:port` of the proxy that served the request.
+    #[serde(default)]
+    pub proxy: Option<String>,
+    /// When the check finished.
+    #[serde(default)]
+    pub checked_at: DateTime<Utc>,
+}
+
+/// What checking a username found.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ResultStatus {
+    /// Nobody has the name.
+    Available,
+    /// Someone has the name.
+    Taken,
+    /// The check failed; see the error for why.
+    Error(CheckError),
+    /// The API answered but didn't say whether the name is available.
+    Unknown,
+    /// Rejected by [`is_valid_username`](crate::is_valid_username) before any request.
+    Invalid,
+}
+
+impl ResultStatus {
+    /// Whether the API gave a final answer for the username.
+    pub fn is_definitive(&self) -> bool {
+        matches!(self, ResultStatus::Available | ResultStatus::Taken)
+    }
+
+    /// Short lowercase name of the outcome, as used in text and NDJSON output.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ResultStatus::Available => "available",
+            ResultStatus::Taken => "taken",
+            ResultStatus::Error(_) => "error",
+            ResultStatus::Unknown => "unknown",
+            ResultStatus::Invalid => "invalid",
+        }
+    }
+
+    /// Whether checking the username again might produce a different outcome.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, ResultStatus::Error(_) | ResultStatus::Unknown)
+    }
+}
+
+/// Running totals for a batch of results.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct Stats {
+    /// Usernames in the run.
+    pub total: usize,
+    /// Usernames with a result so far.
+    pub checked: usize,
+    /// Results that were available.
+    pub available: usize,
+    /// Results that were taken.
+    pub taken: usize,
+    /// Results that were errors, unknown or invalid.
+    pub errors: usize,
+}
+
+impl Stats {
+    /// Counts a result towards its category; `checked` is left to the caller.
+    pub fn add(&mut self, status: &ResultStatus) {
+        match status {
+            ResultStatus::Available => self.available += 1,
+            ResultStatus::Taken => self.taken += 1,
+            ResultStatus::Error(_) | ResultStatus::Unknown | ResultStatus::Invalid => {
+                self.errors += 1
+            }
+        }
+    }
+
+    /// Takes back a result counted by [`Stats::add`], as when it's retried.
+    pub fn remove(&mut self, status: &ResultStatus) {
+        match status {
+            ResultStatus::Available => self.available = self.available.saturating_sub(1),
+            ResultStatus::Taken => self.taken = self.taken.saturating_sub(1),
+            ResultStatus::Error(_) | ResultStatus::Unknown | ResultStatus::Invalid => {
+                self.errors = self.errors.saturating_sub(1)
+            }
+        }
+    }
+}
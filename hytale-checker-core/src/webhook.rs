@@ -0,0 +1,351 @@
+//! Outgoing webhooks for available names and finished runs.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+use crate::types::Stats;
+
+const MAX_ATTEMPTS: u32 = 3;
+const BASE_DELAY: Duration = Duration::from_secs(1);
+
+/// The payload format a webhook expects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookKind {
+    /// A JSON POST, built from `body_template` when one is set.
+    #[default]
+    Generic,
+    /// A Discord channel webhook (`{"content": ...}`).
+    Discord,
+}
+
+/// What a webhook fires on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookTrigger {
+    /// A username came back available.
+    Available,
+    /// A run checked every username.
+    RunComplete,
+}
+
+/// One outgoing webhook.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    /// Where the POST goes.
+    pub url: String,
+    /// The payload format.
+    #[serde(default)]
+    pub kind: WebhookKind,
+    /// What fires this hook; both events by default.
+    #[serde(default = "all_triggers")]
+    pub events: Vec<WebhookTrigger>,
+    /// Body for generic webhooks. `{event}`, `{username}`, `{timestamp}`,
+    /// `{total}`, `{checked}`, `{available}`, `{taken}` and `{errors}` are
+    /// replaced; strings are JSON-escaped but not quoted.
+    #[serde(default)]
+    pub body_template: Option<String>,
+}
+
+fn all_triggers() -> Vec<WebhookTrigger> {
+    vec![WebhookTrigger::Available, WebhookTrigger::RunComplete]
+}
+
+/// Something worth telling a webhook about.
+#[derive(Debug, Clone)]
+pub enum WebhookEvent {
+    /// A username came back available.
+    Available {
+        /// The available name.
+        username: String,
+        /// When it was checked.
+        checked_at: DateTime<Utc>,
+    },
+    /// A run checked every username.
+    RunComplete {
+        /// The run's final totals.
+        stats: Stats,
+    },
+}
+
+impl WebhookEvent {
+    fn trigger(&self) -> WebhookTrigger {
+        match self {
+            WebhookEvent::Available { .. } => WebhookTrigger::Available,
+            WebhookEvent::RunComplete { .. } => WebhookTrigger::RunComplete,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            WebhookEvent::Available { .. } => "available",
+            WebhookEvent::RunComplete { .. } => "run_complete",
+        }
+    }
+
+    fn timestamp(&self) -> String {
+        let at = match self {
+            WebhookEvent::Available { checked_at, .. } => *checked_at,
+            WebhookEvent::RunComplete { .. } => Utc::now(),
+        };
+        at.to_rfc3339_opts(SecondsFormat::Secs, true)
+    }
+}
+
+/// Delivers [`WebhookEvent`]s to every configured webhook, retrying failed
+/// deliveries with backoff.
+pub struct Notifier {
+    client: reqwest::Client,
+    hooks: Vec<WebhookConfig>,
+    base_delay: Duration,
+}
+
+impl Notifier {
+    /// Fails only if the HTTP client can't be set up.
+    pub fn new(hooks: Vec<WebhookConfig>) -> Result<Self, String> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .map_err(|e| format!("Couldn't set up webhooks: {}", e))?;
+        Ok(Self {
+            client,
+            hooks,
+            base_delay: BASE_DELAY,
+        })
+    }
+
+    /// Whether no webhooks are configured.
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    /// Sends `event` to the hooks subscribed to it and returns one message
+    /// per hook that could not be reached.
+    pub async fn notify(&self, event: WebhookEvent) -> Vec<String> {
+        let deliveries = self
+            .hooks
+            .iter()
+            .filter(|hook| hook.events.contains(&event.trigger()))
+            .map(|hook| async {
+                self.deliver(hook, &event)
+                    .await
+                    .err()
+                    .map(|e| format!("{} ({}): {}", hook.url, event.name(), e))
+            });
+
+        futures::future::join_all(deliveries)
+            .await
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    async fn deliver(&self, hook: &WebhookConfig, event: &WebhookEvent) -> Result<(), String> {
+        let body = render_body(hook, event);
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+            let error = match self
+                .client
+                .post(&hook.url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body.clone())
+                .send()
+                .await
+            {
+                Ok(response) if response.status().is_success() => return Ok(()),
+                Ok(response) => {
+                    let status = response.status();
+                    if status.is_client_error() && status.as_u16() != 429 {
+                        return Err(format!("HTTP {}", status.as_u16()));
+                    }
+                    format!("HTTP {}", status.as_u16())
+                }
+                Err(e) => e.to_string(),
+            };
+
+            if attempt >= MAX_ATTEMPTS {
+                return Err(format!("{} after {} attempts", error, attempt));
+            }
+            tokio::time::sleep(self.base_delay * 2u32.pow(attempt - 1)).await;
+        }
+    }
+}
+
+fn render_body(hook: &WebhookConfig, event: &WebhookEvent) -> String {
+    match hook.kind {
+        WebhookKind::Discord => json!({ "content": discord_message(event) }).to_string(),
+        WebhookKind::Generic => match &hook.body_template {
+            Some(template) => render_template(template, event),
+            None => default_body(event).to_string(),
+        },
+    }
+}
+
+fn discord_message(event: &WebhookEvent) -> String {
+    match event {
+        WebhookEvent::Available { username, .. } => format!("✅ **{}** is available", username),
+        WebhookEvent::RunComplete { stats } => format!(
+            "Run complete: {}/{} checked · {} available · {} taken · {} errors",
+            stats.checked, stats.total, stats.available, stats.taken, stats.errors
+        ),
+    }
+}
+
+fn default_body(event: &WebhookEvent) -> Value {
+    match event {
+        WebhookEvent::Available { username, .. } => json!({
+            "event": event.name(),
+            "username": username,
+            "timestamp": event.timestamp(),
+        }),
+        WebhookEvent::RunComplete { stats } => json!({
+            "event": event.name(),
+            "timestamp": event.timestamp(),
+            "stats": stats,
+        }),
+    }
+}
+
+fn render_template(template: &str, event: &WebhookEvent) -> String {
+    let escape = |s: &str| {
+        let quoted = Value::from(s).to_string();
+        quoted[1..quoted.len() - 1].to_string()
+    };
+    let stats = match event {
+        WebhookEvent::RunComplete { stats } => stats.clone(),
+        WebhookEvent::Available { .. } => Stats::default(),
+    };
+    let username = match event {
+        WebhookEvent::Available { username, .. } => username.as_str(),
+        WebhookEvent::RunComplete { .. } => "",
+    };
+
+    template
+        .replace("{event}", event.name())
+        .replace("{username}", &escape(username))
+        .replace("{timestamp}", &event.timestamp())
+        .replace("{total}", &stats.total.to_string())
+        .replace("{checked}", &stats.checked.to_string())
+        .replace("{available}", &stats.available.to_string())
+        .replace("{taken}", &stats.taken.to_string())
+        .replace("{errors}", &stats.errors.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn hook(url: &str, kind: WebhookKind, body_template: Option<&str>) -> WebhookConfig {
+        WebhookConfig {
+            url: url.to_string(),
+            kind,
+            events: all_triggers(),
+            body_template: body_template.map(String::from),
+        }
+    }
+
+    fn notifier(hooks: Vec<WebhookConfig>) -> Notifier {
+        Notifier {
+            base_delay: Duration::from_millis(10),
+            ..Notifier::new(hooks).unwrap()
+        }
+    }
+
+    fn available(username: &str) -> WebhookEvent {
+        WebhookEvent::Available {
+            username: username.to_string(),
+            checked_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn posts_templated_generic_bodies() {
+        let (url, requests) = test_support::serve(200, "").await;
+        let template = r#"{"text": "{username} is {event}", "checked": {checked}}"#;
+        let notifier = notifier(vec![hook(
+            &format!("{}/hooks/1", url),
+            WebhookKind::Generic,
+            Some(template),
+        )]);
+
+        assert!(notifier.notify(available("say\"hi")).await.is_empty());
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/hooks/1");
+        let body: Value = serde_json::from_str(&requests[0].body).unwrap();
+        assert_eq!(
+            body,
+            json!({ "text": "say\"hi is available", "checked": 0 })
+        );
+    }
+
+    #[tokio::test]
+    async fn posts_default_and_discord_bodies() {
+        let (url, requests) = test_support::serve(204, "").await;
+        let notifier = notifier(vec![
+            hook(&url, WebhookKind::Generic, None),
+            hook(&url, WebhookKind::Discord, None),
+        ]);
+        let stats = Stats {
+            total: 3,
+            checked: 3,
+            available: 1,
+            taken: 2,
+            ..Default::default()
+        };
+
+        assert!(notifier
+            .notify(WebhookEvent::RunComplete { stats })
+            .await
+            .is_empty());
+
+        let bodies: Vec<Value> = requests
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|r| serde_json::from_str(&r.body).unwrap())
+            .collect();
+        let generic = bodies.iter().find(|b| b.get("event").is_some()).unwrap();
+        assert_eq!(generic["event"], "run_complete");
+        assert_eq!(generic["stats"]["available"], 1);
+        assert!(generic["timestamp"].is_string());
+        let discord = bodies.iter().find(|b| b.get("content").is_some()).unwrap();
+        assert_eq!(
+            discord["content"],
+            "Run complete: 3/3 checked · 1 available · 2 taken · 0 errors"
+        );
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_three_attempts() {
+        let (url, requests) = test_support::serve(500, "").await;
+        let notifier = notifier(vec![hook(&url, WebhookKind::Discord, None)]);
+
+        let failures = notifier.notify(available("dream")).await;
+
+        assert_eq!(requests.lock().unwrap().len(), MAX_ATTEMPTS as usize);
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].starts_with(&url));
+        assert!(failures[0].ends_with("(available): HTTP 500 after 3 attempts"));
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_client_errors_or_unsubscribed_hooks() {
+        let (url, requests) = test_support::serve(404, "").await;
+        let mut run_only = hook(&url, WebhookKind::Generic, None);
+        run_only.events = vec![WebhookTrigger::RunComplete];
+        let notifier = notifier(vec![hook(&url, WebhookKind::Generic, None), run_only]);
+
+        let failures = notifier.notify(available("dream")).await;
+
+        assert_eq!(requests.lock().unwrap().len(), 1);
+        assert!(failures[0].ends_with("HTTP 404"));
+    }
+}
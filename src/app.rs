@@ -1,32 +1,156 @@
-use chrono::Local;
+use chrono::{DateTime, Local, Utc};
 use iced::widget::{
     button, column, container, pick_list, row, scrollable, space, text, text_editor, text_input,
     Column,
 };
-use iced::{border::Radius, Alignment, Background, Border, Color, Element, Fill, Length, Task};
+use hytale_checker_core::{
+    check_usernames_stream_with_buckets, mutate, score, BackendConfig, CancelHandle, CheckEvent,
+    CheckResult, Concurrency, ErrorKind, JsonBackend, Mutated, Mutation, Notifier, Pattern,
+    PatternError, ProxyType, RateBuckets, RateLimit, ResultStatus, RetryPolicy, Stats,
+    UsernameRules, WebhookEvent,
+};
+use iced::{
+    border::Radius, time, window, Alignment, Background, Border, Color, Element, Fill, Length, Size,
+    Subscription, Task,
+};
 
-use crate::checker::{check_usernames_stream, CancelHandle, CheckEvent, CheckResult, ResultStatus, Stats};
-use crate::proxy::ProxyType;
+use crate::autosave::{self, AutoSave, AutoSaveTarget};
+use crate::checkpoint::{self, Checkpoint, RunSettings};
+use crate::config;
+use crate::export::{self, ExportFormat, ExportOptions, ResultCategory, RunMetadata};
+use crate::history::{self, History, LastKnown, Recorder};
+use crate::preflight::{self, Preflight};
+use crate::settings::{self, Settings, WindowSize};
 use crate::ui::{self, theme};
+use crate::watchlist::{self, Watchlist};
+use crate::wordlist::{self, UsernameList, Wordlist};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(5);
+const WATCH_TICK: Duration = Duration::from_secs(30);
+/// Quiet time after a proxies edit or window resize before settings are saved.
+const SETTINGS_SAVE_DELAY: Duration = Duration::from_millis(750);
+const WATCH_CONCURRENCY: usize = 2;
+/// Larger expansions can only be checked directly, not pasted into the editor.
+const GENERATOR_INSERT_LIMIT: u128 = 100_000;
+const GENERATOR_PREVIEW: usize = 10;
+/// Candidates scanned for the preview, so patterns that are mostly invalid
+/// don't stall the UI.
+const GENERATOR_PREVIEW_SCAN: usize = 10_000;
+const PREFLIGHT_REJECTED_SHOWN: usize = 200;
+/// Wordlist mutations stop here; every name is held in memory for dedup.
+const MUTATION_LIMIT: usize = 1_000_000;
+/// Imported username files longer than this skip the editor, which gets slow
+/// with very long texts, and are checked straight from memory.
+const IMPORT_EDITOR_LIMIT: usize = 5_000;
+const PATTERN_HELP: &str =
+    "Letters, digits and _ are literal. [a-z] class, [c] or \\c consonant, [v] or \\v vowel, \
+    \\l letter, \\d digit, {2} or {1,3} repeat, (a|b|) alternatives, <1-99> or <01-99> numbers";
 
 #[derive(Debug, Clone)]
 pub enum Message {
     UsernamesChanged(text_editor::Action),
     ProxiesChanged(text_editor::Action),
     ProxyTypeChanged(ProxyType),
-    DelayChanged(String),
+    RateChanged(String),
+    ProxyRateChanged(String),
     ConcurrencyChanged(String),
+    MaxAttemptsChanged(String),
+    SkipTakenDaysChanged(String),
+    ToggleAdaptiveConcurrency,
     TabChanged(Tab),
+    ErrorFilterChanged(Option<ErrorKind>),
+    SortByScoreChanged(bool),
     ToggleProxyPanel,
+    ChooseAutoSave(AutoSaveTarget),
+    AutoSaveChosen(AutoSaveTarget, Option<PathBuf>),
+    ClearAutoSave(AutoSaveTarget),
     StartCheck,
+    /// The preflight of a list about to be checked by the run with the
+    /// given generation.
+    PreflightDone(u64, UsernameList, Result<Preflight, String>),
+    ConfirmPreflight,
+    CancelPreflight,
+    ApplyPreflight,
     StopCheck,
-    CheckEventReceived(CheckEvent),
+    PauseCheck,
+    ResumeCheck,
+    /// An event from the run with the given generation.
+    CheckEventReceived(u64, CheckEvent),
+    /// History for a list about to be checked with recently taken names skipped.
+    HistoryLookedUp(
+        u64,
+        UsernameList,
+        Preflight,
+        Result<HashMap<String, LastKnown>, String>,
+    ),
+    /// History for the names in a run that's already going.
+    LastKnownLoaded(u64, Result<HashMap<String, LastKnown>, String>),
+    OpenExportDialog,
+    CloseExportDialog,
+    ExportCategoryToggled(ResultCategory),
+    ExportFormatChanged(ExportFormat),
     ExportResults,
     ExportComplete(Result<String, String>),
     ClearResults,
+    RetryErrors,
+    OpenWatchlist,
+    CloseWatchlist,
+    WatchInputChanged(String),
+    WatchIntervalChanged(String),
+    AddToWatchlist,
+    RemoveFromWatchlist(String),
+    CheckWatchlistNow,
+    WatchTick,
+    WatchEventReceived(CheckEvent),
+    DismissWatchAlerts,
+    OpenGenerator,
+    CloseGenerator,
+    GeneratorModeChanged(GeneratorMode),
+    GeneratorInputChanged(String),
+    ChooseWordlist,
+    WordlistChosen(Option<PathBuf>),
+    WordlistLoaded(Result<Wordlist, String>),
+    MutationToggled(Mutation),
+    MutationsGenerated(Vec<Mutation>, Arc<Mutated>),
+    InsertGenerated,
+    CheckGenerated,
+    ImportFile(ImportTarget),
+    ImportChosen(ImportTarget, Option<PathBuf>),
+    UsernamesLoaded(Result<UsernameList, String>),
+    ProxiesLoaded(Result<Wordlist, String>),
+    FileDropped(PathBuf),
+    ClearImport,
+    WebhooksDelivered(Vec<String>),
+    WindowResized(Size),
+    CloseRequested,
+    SettingsSaveDue(u64),
+    CheckpointLoaded(Option<Checkpoint>),
+    CheckpointSaved(Result<(), String>),
+    ResumeCheckpoint,
+    DiscardCheckpoint,
+}
+
+/// A result as listed, with an available name's score worked out once when
+/// it arrives rather than on every render.
+struct ResultRow {
+    result: CheckResult,
+    score: Option<u8>,
+}
+
+impl ResultRow {
+    fn new(result: CheckResult) -> Self {
+        let score =
+            (result.status == ResultStatus::Available).then(|| score(&result.username).total);
+        Self { result, score }
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum Tab {
     #[default]
     All,
@@ -35,39 +159,213 @@ pub enum Tab {
     Errors,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeneratorMode {
+    Pattern,
+    Wordlist,
+}
+
+/// Which editor an imported file fills.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportTarget {
+    Usernames,
+    Proxies,
+}
+
 pub struct App {
     usernames_content: text_editor::Content,
+    /// A username file too long for the editor; checked instead of the
+    /// editor's text while set.
+    imported_usernames: Option<UsernameList>,
     proxies_content: text_editor::Content,
     proxy_type: ProxyType,
-    delay_ms: String,
+    rate_limit: String,
+    proxy_rate_limit: String,
     concurrency: String,
+    max_attempts: String,
+    skip_taken_days: String,
+    adaptive_concurrency: bool,
+    effective_concurrency: Option<usize>,
     current_tab: Tab,
+    error_filter: Option<ErrorKind>,
+    sort_by_score: bool,
     is_checking: bool,
-    results: Vec<CheckResult>,
+    is_paused: bool,
+    is_retrying: bool,
+    results: Vec<ResultRow>,
     stats: Stats,
     status_message: String,
     show_proxy_panel: bool,
     cancel_handle: Option<CancelHandle>,
+    /// Bumped when a run starts or stops, so events still buffered from an
+    /// earlier run are dropped.
+    run_generation: u64,
+    /// The list a run reads, kept for its checkpoint.
+    run_usernames: Option<UsernameList>,
+    /// Names the run left out as recently taken.
+    run_skipped: Arc<HashSet<String>>,
+    run_pattern: Option<String>,
+    last_checkpoint: Option<Instant>,
+    pending_checkpoint: Option<Checkpoint>,
+    /// How many usernames `pending_checkpoint` covers, worked out on load.
+    pending_total: usize,
+    backend: BackendConfig,
+    rules: UsernameRules,
+    show_export_dialog: bool,
+    export_options: ExportOptions,
+    run_started_at: Option<DateTime<Local>>,
+    run_finished_at: Option<DateTime<Local>>,
+    hits_file: Option<PathBuf>,
+    errors_file: Option<PathBuf>,
+    ndjson_file: Option<PathBuf>,
+    autosave: Option<AutoSave>,
+    window_size: WindowSize,
+    saved_settings: Settings,
+    /// Bumped on every proxies edit and window resize; only the last one's
+    /// timer saves.
+    settings_edits: u64,
+    history: Option<History>,
+    /// Writes results to `history` off the UI thread.
+    recorder: Option<Recorder>,
+    history_run: Option<i64>,
+    last_known: HashMap<String, LastKnown>,
+    watchlist: Watchlist,
+    show_watchlist: bool,
+    watch_input: String,
+    watch_interval: String,
+    watch_cancel: Option<CancelHandle>,
+    /// The global and per-proxy rate caps, shared by the main and watch runs
+    /// so together they stay under them.
+    rate_buckets: RateBuckets,
+    watch_run: Option<i64>,
+    watch_alerts: Vec<String>,
+    notifier: Option<Arc<Notifier>>,
+    show_generator: bool,
+    generator_mode: GeneratorMode,
+    generator_input: String,
+    generator: Option<Result<Pattern, PatternError>>,
+    generator_preview: Vec<String>,
+    /// Valid names `generator` yields; counted once per edit since
+    /// deduplicating can take a moment.
+    generator_valid: u128,
+    wordlist: Option<Wordlist>,
+    mutations: Vec<Mutation>,
+    mutated: Option<Arc<Mutated>>,
+    preflight: Option<(UsernameList, Preflight)>,
 }
 
 impl App {
-    pub fn new() -> (Self, Task<Message>) {
-        (
-            Self {
-                usernames_content: text_editor::Content::new(),
-                proxies_content: text_editor::Content::new(),
-                proxy_type: ProxyType::None,
-                delay_ms: "100".to_string(),
-                concurrency: "5".to_string(),
-                current_tab: Tab::All,
-                is_checking: false,
-                results: Vec::new(),
-                stats: Stats::default(),
-                status_message: String::new(),
-                show_proxy_panel: false,
-                cancel_handle: None,
+    pub fn new(settings: Result<Settings, String>) -> (Self, Task<Message>) {
+        let (backend, backend_error) = match config::load_backend() {
+            Ok(backend) => (backend, None),
+            Err(e) => (BackendConfig::default(), Some(e)),
+        };
+        let (settings, settings_error) = match settings {
+            Ok(settings) => (settings, None),
+            Err(e) => (Settings::default(), Some(e)),
+        };
+        let history =
+            History::open().and_then(|history| Ok((history, Recorder::start(History::open()?)?)));
+        let (history, recorder, history_error) = match history {
+            Ok((history, recorder)) => (Some(history), Some(recorder), None),
+            Err(e) => (None, None, Some(format!("History unavailable: {}", e))),
+        };
+        let (watchlist, watchlist_error) = match watchlist::load() {
+            Ok(watchlist) => (watchlist, None),
+            Err(e) => (Watchlist::default(), Some(e)),
+        };
+        let (notifier, webhooks_error) = match config::load_webhooks() {
+            Ok(hooks) if hooks.is_empty() => (None, None),
+            Ok(hooks) => match Notifier::new(hooks) {
+                Ok(notifier) => (Some(Arc::new(notifier)), None),
+                Err(e) => (None, Some(e)),
             },
-            Task::none(),
+            Err(e) => (None, Some(e)),
+        };
+        let (rules, rules_error) = match config::load_rules() {
+            Ok(rules) => (rules, None),
+            Err(e) => (UsernameRules::default(), Some(e)),
+        };
+        let status_message = backend_error
+            .or(rules_error)
+            .or(settings_error)
+            .or(history_error)
+            .or(watchlist_error)
+            .or(webhooks_error)
+            .unwrap_or_default();
+
+        let mut app = Self {
+            usernames_content: text_editor::Content::new(),
+            imported_usernames: None,
+            proxies_content: text_editor::Content::new(),
+            proxy_type: ProxyType::None,
+            rate_limit: String::new(),
+            proxy_rate_limit: String::new(),
+            concurrency: String::new(),
+            max_attempts: String::new(),
+            skip_taken_days: String::new(),
+            adaptive_concurrency: false,
+            effective_concurrency: None,
+            current_tab: settings.last_tab,
+            error_filter: None,
+            sort_by_score: false,
+            is_checking: false,
+            is_paused: false,
+            is_retrying: false,
+            results: Vec::new(),
+            stats: Stats::default(),
+            status_message,
+            show_proxy_panel: settings.show_proxy_panel,
+            cancel_handle: None,
+            run_generation: 0,
+            run_usernames: None,
+            run_skipped: Arc::default(),
+            run_pattern: None,
+            last_checkpoint: None,
+            pending_checkpoint: None,
+            pending_total: 0,
+            backend,
+            rules,
+            show_export_dialog: false,
+            export_options: ExportOptions::default(),
+            run_started_at: None,
+            run_finished_at: None,
+            hits_file: None,
+            errors_file: None,
+            ndjson_file: None,
+            autosave: None,
+            window_size: settings.window,
+            saved_settings: settings.clone(),
+            settings_edits: 0,
+            history,
+            recorder,
+            history_run: None,
+            last_known: HashMap::new(),
+            watch_interval: watchlist.interval_minutes.to_string(),
+            watchlist,
+            show_watchlist: false,
+            watch_input: String::new(),
+            watch_cancel: None,
+            rate_buckets: RateBuckets::default(),
+            watch_run: None,
+            watch_alerts: Vec::new(),
+            notifier,
+            show_generator: false,
+            generator_mode: GeneratorMode::Pattern,
+            generator_input: String::new(),
+            generator: None,
+            generator_preview: Vec::new(),
+            generator_valid: 0,
+            wordlist: None,
+            mutations: Vec::new(),
+            mutated: None,
+            preflight: None,
+        };
+        app.apply_settings(&settings.run);
+
+        (
+            app,
+            Task::perform(checkpoint::load(), Message::CheckpointLoaded),
         )
     }
 
@@ -78,155 +376,1781 @@ impl App {
                 Task::none()
             }
             Message::ProxiesChanged(action) => {
+                let is_edit = action.is_edit();
                 self.proxies_content.perform(action);
+                if !is_edit {
+                    return Task::none();
+                }
+                self.save_settings_later()
+            }
+            Message::SettingsSaveDue(edit) => {
+                if edit == self.settings_edits {
+                    self.save_settings();
+                }
                 Task::none()
             }
             Message::ProxyTypeChanged(proxy_type) => {
                 self.proxy_type = proxy_type;
+                self.save_settings();
+                Task::none()
+            }
+            Message::RateChanged(value) => {
+                if is_valid_rate(&value) {
+                    self.rate_limit = value;
+                    self.save_settings();
+                }
                 Task::none()
             }
-            Message::DelayChanged(value) => {
-                if value.is_empty() || value.parse::<u64>().is_ok() {
-                    self.delay_ms = value;
+            Message::ProxyRateChanged(value) => {
+                if is_valid_rate(&value) {
+                    self.proxy_rate_limit = value;
+                    self.save_settings();
                 }
                 Task::none()
             }
             Message::ConcurrencyChanged(value) => {
                 if value.is_empty() || value.parse::<usize>().is_ok() {
                     self.concurrency = value;
+                    self.save_settings();
+                }
+                Task::none()
+            }
+            Message::MaxAttemptsChanged(value) => {
+                if value.is_empty() || value.parse::<u32>().is_ok() {
+                    self.max_attempts = value;
+                    self.save_settings();
+                }
+                Task::none()
+            }
+            Message::SkipTakenDaysChanged(value) => {
+                if value.is_empty() || value.parse::<u32>().is_ok() {
+                    self.skip_taken_days = value;
+                    self.save_settings();
                 }
                 Task::none()
             }
+            Message::ToggleAdaptiveConcurrency => {
+                self.adaptive_concurrency = !self.adaptive_concurrency;
+                self.save_settings();
+                Task::none()
+            }
             Message::TabChanged(tab) => {
                 self.current_tab = tab;
+                self.save_settings();
+                Task::none()
+            }
+            Message::ErrorFilterChanged(filter) => {
+                self.error_filter = filter;
+                Task::none()
+            }
+            Message::SortByScoreChanged(sort) => {
+                self.sort_by_score = sort;
                 Task::none()
             }
             Message::ToggleProxyPanel => {
                 self.show_proxy_panel = !self.show_proxy_panel;
+                self.save_settings();
+                Task::none()
+            }
+            Message::ChooseAutoSave(target) => {
+                Task::perform(autosave::choose_file(target), move |path| {
+                    Message::AutoSaveChosen(target, path)
+                })
+            }
+            Message::AutoSaveChosen(target, path) => {
+                if path.is_some() {
+                    *self.autosave_path(target) = path;
+                }
+                Task::none()
+            }
+            Message::ClearAutoSave(target) => {
+                *self.autosave_path(target) = None;
                 Task::none()
             }
             Message::StartCheck => {
-                let usernames: Vec<String> = self
-                    .usernames_content
-                    .text()
-                    .lines()
-                    .map(|s| s.trim().to_string())
-                    .filter(|s| !s.is_empty())
-                    .collect();
-
-                if usernames.is_empty() {
+                let list = match &self.imported_usernames {
+                    Some(list) => list.clone(),
+                    None => UsernameList::from_words(
+                        "the editor",
+                        self.usernames_content
+                            .text()
+                            .lines()
+                            .map(str::to_string)
+                            .collect(),
+                    ),
+                };
+                if list.len == 0 {
                     self.status_message = "Enter usernames to check".to_string();
                     return Task::none();
                 }
 
+                self.run_generation += 1;
+                let generation = self.run_generation;
                 self.is_checking = true;
-                self.results.clear();
-                self.stats = Stats {
-                    total: usernames.len(),
-                    ..Default::default()
-                };
-                self.status_message = format!("Checking {} usernames...", usernames.len());
-
-                let delay = self.delay_ms.parse().unwrap_or(100);
-                let concurrency = self.concurrency.parse().unwrap_or(5).max(1);
-
-                let proxies: Vec<String> = if self.proxy_type != ProxyType::None {
-                    self.proxies_content
-                        .text()
-                        .lines()
-                        .map(|s| s.trim().to_string())
-                        .filter(|s| !s.is_empty())
-                        .map(|s| self.proxy_type.format_proxy(&s))
-                        .collect()
-                } else {
-                    Vec::new()
+                self.status_message = format!("Reading {} lines...", list.len);
+                let preflight = preflight_list(list.clone(), self.rules.clone());
+                Task::perform(preflight, move |preflight| {
+                    Message::PreflightDone(generation, list, preflight)
+                })
+            }
+            Message::PreflightDone(generation, list, preflight) => {
+                if generation != self.run_generation {
+                    return Task::none();
+                }
+                self.is_checking = false;
+                let preflight = match preflight {
+                    Ok(preflight) => preflight,
+                    Err(e) => {
+                        self.status_message = format!("Couldn't read usernames: {}", e);
+                        return Task::none();
+                    }
                 };
+                if preflight.lines == 0 {
+                    self.status_message = "Enter usernames to check".to_string();
+                    return Task::none();
+                }
 
-                let (rx, cancel_handle) = check_usernames_stream(usernames, proxies, delay, concurrency);
-                self.cancel_handle = Some(cancel_handle);
-                Task::run(rx, Message::CheckEventReceived)
+                if preflight.needs_review() {
+                    self.preflight = Some((list, preflight));
+                    return Task::none();
+                }
+                self.start_list(list, preflight)
+            }
+            Message::ConfirmPreflight => {
+                let Some((list, preflight)) = self.preflight.take() else {
+                    return Task::none();
+                };
+                if preflight.valid == 0 || self.is_checking {
+                    return Task::none();
+                }
+                self.start_list(list, preflight)
+            }
+            Message::CancelPreflight => {
+                self.preflight = None;
+                Task::none()
+            }
+            Message::ApplyPreflight => {
+                // A file is left as it is on disk; only lists held in memory
+                // are cleaned up.
+                if let Some((list, preflight)) = self.preflight.take()
+                    && list.file.is_none()
+                {
+                    self.status_message = preflight.summary();
+                    let usernames: Vec<String> =
+                        preflight::usernames(list.words.iter(), self.rules.clone()).collect();
+                    match &mut self.imported_usernames {
+                        Some(imported) => {
+                            *imported = UsernameList::from_words(imported.name.clone(), usernames)
+                        }
+                        None => {
+                            self.usernames_content =
+                                text_editor::Content::with_text(&usernames.join("\n"))
+                        }
+                    }
+                }
+                Task::none()
             }
             Message::StopCheck => {
                 if let Some(handle) = self.cancel_handle.take() {
                     handle.cancel();
                 }
+                self.run_generation += 1;
                 self.is_checking = false;
+                self.is_paused = false;
+                self.is_retrying = false;
+                self.run_finished_at = Some(Local::now());
+                self.autosave = None;
                 self.status_message = "Stopped".to_string();
+                self.save_checkpoint()
+            }
+            Message::PauseCheck => {
+                if let Some(handle) = &self.cancel_handle {
+                    handle.pause();
+                    self.is_paused = true;
+                    self.status_message = "Paused".to_string();
+                }
+                Task::none()
+            }
+            Message::ResumeCheck => {
+                if let Some(handle) = &self.cancel_handle {
+                    handle.resume();
+                    self.is_paused = false;
+                    self.status_message = format!(
+                        "Checking {} usernames...",
+                        self.stats.total - self.stats.checked
+                    );
+                }
+                Task::none()
+            }
+            Message::HistoryLookedUp(generation, list, preflight, known) => {
+                if generation != self.run_generation {
+                    return Task::none();
+                }
+                self.is_checking = false;
+                self.apply_last_known(known);
+                self.launch_list(list, preflight)
+            }
+            Message::LastKnownLoaded(generation, known) => {
+                if generation == self.run_generation {
+                    self.apply_last_known(known);
+                }
+                Task::none()
+            }
+            Message::CheckEventReceived(generation, _) if generation != self.run_generation => {
                 Task::none()
             }
-            Message::CheckEventReceived(event) => {
+            Message::CheckEventReceived(_, event) => {
                 match event {
                     CheckEvent::Result(result) => {
-                        match &result.status {
-                            ResultStatus::Available => self.stats.available += 1,
-                            ResultStatus::Taken => self.stats.taken += 1,
-                            ResultStatus::Error(_) | ResultStatus::Invalid => self.stats.errors += 1,
+                        self.record(&result);
+
+                        let notify = if result.status == ResultStatus::Available {
+                            self.notify(WebhookEvent::Available {
+                                username: result.username.clone(),
+                                checked_at: result.checked_at,
+                            })
+                        } else {
+                            Task::none()
+                        };
+
+                        if self.is_retrying {
+                            if let Some(existing) = self.results.iter_mut().find(|row| {
+                                row.result.username == result.username
+                                    && row.result.status.is_retryable()
+                            }) {
+                                self.stats.remove(&existing.result.status);
+                                self.stats.add(&result.status);
+                                *existing = ResultRow::new(result);
+                            }
+                        } else {
+                            self.stats.add(&result.status);
+                            self.stats.checked += 1;
+                            self.results.push(ResultRow::new(result));
+                        }
+
+                        if self
+                            .last_checkpoint
+                            .is_none_or(|at| at.elapsed() >= CHECKPOINT_INTERVAL)
+                        {
+                            return Task::batch([notify, self.save_checkpoint()]);
                         }
-                        self.stats.checked += 1;
-                        self.results.push(result);
+                        return notify;
+                    }
+                    CheckEvent::Concurrency(limit) => {
+                        self.effective_concurrency = Some(limit);
                     }
                     CheckEvent::Done => {
+                        let was_retry = self.is_retrying;
                         self.is_checking = false;
+                        self.is_paused = false;
+                        self.is_retrying = false;
+                        self.run_finished_at = Some(Local::now());
+                        self.autosave = None;
+                        // Retrying the errors of a stopped run leaves the rest
+                        // of that run unchecked, so it stays resumable.
+                        if was_retry && self.has_resumable_run() {
+                            self.status_message =
+                                "Retried errors; the stopped run is still unfinished".to_string();
+                            return self.save_checkpoint();
+                        }
                         self.status_message = "Complete".to_string();
+                        // Nothing is left to resume once every name has a result.
+                        self.run_usernames = None;
+                        self.run_skipped = Arc::default();
+                        self.run_pattern = None;
+                        return Task::batch([
+                            remove_checkpoint(),
+                            self.notify(WebhookEvent::RunComplete {
+                                stats: self.stats.clone(),
+                            }),
+                        ]);
+                    }
+                }
+                Task::none()
+            }
+            Message::OpenExportDialog => {
+                self.show_export_dialog = true;
+                Task::none()
+            }
+            Message::CloseExportDialog => {
+                self.show_export_dialog = false;
+                Task::none()
+            }
+            Message::ExportCategoryToggled(category) => {
+                self.export_options.toggle(category);
+                Task::none()
+            }
+            Message::ExportFormatChanged(format) => {
+                self.export_options.format = format;
+                Task::none()
+            }
+            Message::ExportResults => {
+                let selected: Vec<&CheckResult> = self
+                    .results
+                    .iter()
+                    .map(|row| &row.result)
+                    .filter(|r| self.export_options.includes(&r.status))
+                    .collect();
+
+                if selected.is_empty() {
+                    self.status_message = "No results in the selected categories".to_string();
+                    return Task::none();
+                }
+
+                let metadata = RunMetadata {
+                    settings: self.run_settings(),
+                    started_at: self.run_started_at,
+                    finished_at: self.run_finished_at,
+                    stats: self.stats.clone(),
+                };
+                let format = self.export_options.format;
+                let content = match export::render(&selected, format, &metadata) {
+                    Ok(content) => content,
+                    Err(e) => {
+                        self.status_message = format!("Export failed: {}", e);
+                        return Task::none();
+                    }
+                };
+                let name = match self.export_options.categories.as_slice() {
+                    [ResultCategory::Available] => "available",
+                    _ => "results",
+                };
+
+                self.show_export_dialog = false;
+                Task::perform(
+                    export::save_to_file(content, format, name),
+                    Message::ExportComplete,
+                )
+            }
+            Message::ExportComplete(result) => {
+                self.status_message = match result {
+                    Ok(path) => format!("Saved: {}", path),
+                    Err(e) => e,
+                };
+                Task::none()
+            }
+            Message::ClearResults => {
+                self.results.clear();
+                self.stats = Stats::default();
+                self.status_message.clear();
+                self.run_usernames = None;
+                self.run_skipped = Arc::default();
+                self.run_pattern = None;
+                self.run_started_at = None;
+                self.run_finished_at = None;
+                remove_checkpoint()
+            }
+            Message::RetryErrors => {
+                let failed: Vec<String> = self
+                    .results
+                    .iter()
+                    .map(|row| &row.result)
+                    .filter(|r| r.status.is_retryable())
+                    .map(|r| r.username.clone())
+                    .collect();
+
+                if failed.is_empty() {
+                    self.status_message = "No errors to retry".to_string();
+                    return Task::none();
+                }
+
+                let count = failed.len();
+                let task = self.launch(failed, count);
+                if self.is_checking {
+                    self.is_retrying = true;
+                    self.status_message = format!("Retrying {} errors...", count);
+                }
+                task
+            }
+            Message::OpenWatchlist => {
+                self.show_watchlist = true;
+                Task::none()
+            }
+            Message::CloseWatchlist => {
+                self.show_watchlist = false;
+                Task::none()
+            }
+            Message::WatchInputChanged(value) => {
+                self.watch_input = value;
+                Task::none()
+            }
+            Message::WatchIntervalChanged(value) => {
+                if value.is_empty() || value.parse::<u32>().is_ok() {
+                    if let Ok(minutes) = value.parse::<u32>()
+                        && minutes > 0
+                    {
+                        self.watchlist.interval_minutes = minutes;
+                        self.save_watchlist();
+                    }
+                    self.watch_interval = value;
+                }
+                Task::none()
+            }
+            Message::AddToWatchlist => {
+                let username = self.watch_input.trim().to_string();
+                if !self.rules.is_valid(&username) {
+                    self.status_message = format!("\"{}\" isn't a valid username", username);
+                    return Task::none();
+                }
+
+                if self.watchlist.add(&username) {
+                    self.save_watchlist();
+                }
+                self.watch_input.clear();
+                Task::none()
+            }
+            Message::RemoveFromWatchlist(username) => {
+                self.watchlist.remove(&username);
+                self.save_watchlist();
+                Task::none()
+            }
+            Message::CheckWatchlistNow => {
+                let usernames = self
+                    .watchlist
+                    .entries
+                    .iter()
+                    .map(|e| e.username.clone())
+                    .collect();
+                self.start_watch(usernames)
+            }
+            Message::WatchTick => {
+                // Watch runs share the rate caps with a regular run, so they
+                // don't wait for it to finish.
+                let due = self.watchlist.due(Utc::now());
+                if due.is_empty() {
+                    return Task::none();
+                }
+                self.start_watch(due)
+            }
+            Message::WatchEventReceived(event) => {
+                match event {
+                    CheckEvent::Result(result) => {
+                        if let (Some(recorder), Some(run_id)) = (&self.recorder, self.watch_run)
+                            && let Err(e) = recorder.record(run_id, &result)
+                        {
+                            self.status_message = format!("History disabled: {}", e);
+                            self.history = None;
+                            self.recorder = None;
+                        }
+
+                        let released = self.watchlist.apply(&result);
+                        self.save_watchlist();
+                        if released {
+                            self.watch_alerts
+                                .push(format!("{} is now available", result.username));
+                            return self.notify(WebhookEvent::Available {
+                                username: result.username,
+                                checked_at: result.checked_at,
+                            });
+                        }
+                    }
+                    CheckEvent::Concurrency(_) => {}
+                    CheckEvent::Done => {
+                        self.watch_cancel = None;
+                        self.watch_run = None;
                     }
                 }
-                Task::none()
+                Task::none()
+            }
+            Message::DismissWatchAlerts => {
+                self.watch_alerts.clear();
+                Task::none()
+            }
+            Message::OpenGenerator => {
+                self.show_generator = true;
+                Task::none()
+            }
+            Message::CloseGenerator => {
+                self.show_generator = false;
+                Task::none()
+            }
+            Message::GeneratorModeChanged(mode) => {
+                self.generator_mode = mode;
+                Task::none()
+            }
+            Message::GeneratorInputChanged(value) => {
+                self.generator = (!value.trim().is_empty()).then(|| Pattern::parse(&value));
+                let mut seen = HashSet::new();
+                self.generator_preview = match &self.generator {
+                    Some(Ok(pattern)) => pattern
+                        .clone()
+                        .candidates()
+                        .take(GENERATOR_PREVIEW_SCAN)
+                        .filter(|name| self.rules.is_valid(name) && seen.insert(name.clone()))
+                        .take(GENERATOR_PREVIEW)
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                self.generator_valid = match &self.generator {
+                    Some(Ok(pattern)) => pattern.valid_count(&self.rules),
+                    _ => 0,
+                };
+                self.generator_input = value;
+                Task::none()
+            }
+            Message::ChooseWordlist => {
+                Task::perform(wordlist::choose_file(), Message::WordlistChosen)
+            }
+            Message::WordlistChosen(path) => match path {
+                Some(path) => Task::perform(wordlist::load(path), Message::WordlistLoaded),
+                None => Task::none(),
+            },
+            Message::WordlistLoaded(result) => match result {
+                Ok(wordlist) => {
+                    self.wordlist = Some(wordlist);
+                    self.mutated = None;
+                    self.generate_mutations()
+                }
+                Err(e) => {
+                    self.status_message = format!("Couldn't read wordlist: {}", e);
+                    Task::none()
+                }
+            },
+            Message::MutationToggled(mutation) => {
+                if let Some(i) = self.mutations.iter().position(|m| *m == mutation) {
+                    self.mutations.remove(i);
+                } else {
+                    self.mutations.push(mutation);
+                }
+                self.generate_mutations()
+            }
+            Message::MutationsGenerated(mutations, mutated) => {
+                // Drop results for a selection that has since changed.
+                if mutations == self.mutations {
+                    self.mutated = Some(mutated);
+                }
+                Task::none()
+            }
+            Message::InsertGenerated => {
+                let names: Box<dyn Iterator<Item = String>> = match self.generator_mode {
+                    GeneratorMode::Pattern => match &self.generator {
+                        Some(Ok(pattern)) if self.generator_valid <= GENERATOR_INSERT_LIMIT => {
+                            Box::new(pattern.clone().valid_names(self.rules.clone()))
+                        }
+                        _ => return Task::none(),
+                    },
+                    GeneratorMode::Wordlist => match &self.mutated {
+                        Some(mutated) if mutated.names.len() as u128 <= GENERATOR_INSERT_LIMIT => {
+                            Box::new(mutated.names.clone().into_iter())
+                        }
+                        _ => return Task::none(),
+                    },
+                };
+
+                if let Some(list) = &mut self.imported_usernames {
+                    let words = Arc::make_mut(&mut list.words);
+                    let before = words.len();
+                    words.extend(names);
+                    let added = words.len() - before;
+                    list.len += added;
+                    self.status_message = format!("Added {} generated usernames", added);
+                    self.show_generator = false;
+                    return Task::none();
+                }
+
+                let mut text = self.usernames_content.text();
+                if !text.trim().is_empty() && !text.ends_with('\n') {
+                    text.push('\n');
+                }
+                let before = text.lines().count();
+                for name in names {
+                    text.push_str(&name);
+                    text.push('\n');
+                }
+                self.status_message = format!(
+                    "Added {} generated usernames",
+                    text.lines().count() - before
+                );
+                self.usernames_content = text_editor::Content::with_text(&text);
+                self.show_generator = false;
+                Task::none()
+            }
+            Message::CheckGenerated => {
+                if self.is_checking {
+                    return Task::none();
+                }
+                if self.generator_mode == GeneratorMode::Wordlist {
+                    let Some(mutated) = &self.mutated else {
+                        return Task::none();
+                    };
+                    // Mutations are already lowercased, deduplicated and valid.
+                    let count = mutated.names.len();
+                    let preflight = Preflight {
+                        lines: count,
+                        valid: count,
+                        ..Default::default()
+                    };
+                    let list = UsernameList::from_words("the generator", mutated.names.clone());
+                    self.show_generator = false;
+                    return self.start_list(list, preflight);
+                }
+                let Some(Ok(pattern)) = &self.generator else {
+                    return Task::none();
+                };
+
+                let count = usize::try_from(self.generator_valid).unwrap_or(usize::MAX);
+                if count == 0 {
+                    return Task::none();
+                }
+
+                self.results.clear();
+                self.stats = Stats {
+                    total: count,
+                    ..Default::default()
+                };
+                self.pending_checkpoint = None;
+                self.last_known.clear();
+                self.run_usernames = None;
+                self.run_skipped = Arc::default();
+                self.run_pattern = Some(self.generator_input.trim().to_string());
+                self.run_started_at = Some(Local::now());
+                self.show_generator = false;
+                let names = pattern.clone().valid_names(self.rules.clone());
+                self.launch(names, count)
+            }
+            Message::ImportFile(target) => Task::perform(wordlist::choose_file(), move |path| {
+                Message::ImportChosen(target, path)
+            }),
+            Message::ImportChosen(target, path) => match (target, path) {
+                (ImportTarget::Usernames, Some(path)) => Task::perform(
+                    wordlist::load_usernames(path, IMPORT_EDITOR_LIMIT),
+                    Message::UsernamesLoaded,
+                ),
+                (ImportTarget::Proxies, Some(path)) => {
+                    Task::perform(wordlist::load(path), Message::ProxiesLoaded)
+                }
+                (_, None) => Task::none(),
+            },
+            Message::FileDropped(path) => Task::perform(
+                wordlist::load_usernames(path, IMPORT_EDITOR_LIMIT),
+                Message::UsernamesLoaded,
+            ),
+            Message::UsernamesLoaded(result) => {
+                match result {
+                    Ok(list) => self.import_usernames(list),
+                    Err(e) => self.status_message = format!("Couldn't import file: {}", e),
+                }
+                Task::none()
+            }
+            Message::ProxiesLoaded(result) => {
+                match result {
+                    Ok(file) => self.import_proxies(file),
+                    Err(e) => self.status_message = format!("Couldn't import file: {}", e),
+                }
+                Task::none()
+            }
+            Message::ClearImport => {
+                self.imported_usernames = None;
+                Task::none()
+            }
+            Message::WebhooksDelivered(failures) => {
+                if let Some(failure) = failures.first() {
+                    config::log_webhook_failures(&failures);
+                    self.status_message = format!("Webhook failed: {}", failure);
+                }
+                Task::none()
+            }
+            Message::WindowResized(size) => {
+                self.window_size = WindowSize {
+                    width: size.width,
+                    height: size.height,
+                };
+                self.save_settings_later()
+            }
+            Message::CloseRequested => {
+                if let Some(handle) = self.cancel_handle.take() {
+                    handle.cancel();
+                }
+                if let Some(handle) = self.watch_cancel.take() {
+                    handle.cancel();
+                }
+                self.save_settings();
+                // Results still queued for history are written before exiting.
+                let recorder = self.recorder.take();
+                let flush_history = Task::future(tokio::task::spawn_blocking(move || {
+                    recorder.map(Recorder::finish)
+                }))
+                .discard();
+                Task::batch([self.save_checkpoint(), flush_history]).chain(iced::exit())
+            }
+            Message::CheckpointLoaded(checkpoint) => {
+                self.pending_total = checkpoint.as_ref().map_or(0, |c| c.total(&self.rules));
+                self.pending_checkpoint = checkpoint;
+                Task::none()
+            }
+            Message::CheckpointSaved(result) => {
+                if let Err(e) = result {
+                    self.status_message = format!("Checkpoint failed: {}", e);
+                }
+                // A save still in flight when the run finished can land after
+                // the removal; don't leave it behind.
+                if self.has_resumable_run() {
+                    Task::none()
+                } else {
+                    remove_checkpoint()
+                }
+            }
+            Message::ResumeCheckpoint => {
+                let Some(checkpoint) = self.pending_checkpoint.take() else {
+                    return Task::none();
+                };
+
+                self.apply_settings(&checkpoint.settings);
+                self.save_settings();
+                self.last_known.clear();
+                if checkpoint.pattern.is_none() {
+                    let mut list = UsernameList::from_words(
+                        "the unfinished run",
+                        checkpoint.usernames.clone(),
+                    );
+                    if checkpoint.file.is_some() {
+                        list.file = checkpoint.file.clone();
+                        list.len = self.pending_total;
+                    }
+                    self.import_usernames(list);
+                }
+                self.results = checkpoint
+                    .definitive_results()
+                    .cloned()
+                    .map(ResultRow::new)
+                    .collect();
+                self.stats = Stats {
+                    total: self.pending_total,
+                    checked: self.results.len(),
+                    ..Default::default()
+                };
+                for row in &self.results {
+                    self.stats.add(&row.result.status);
+                }
+
+                let remaining_count = self.stats.total.saturating_sub(self.stats.checked);
+                self.run_started_at = Some(Local::now());
+                if remaining_count == 0 {
+                    self.status_message = "Complete".to_string();
+                    return remove_checkpoint();
+                }
+
+                let done: HashSet<String> = self
+                    .results
+                    .iter()
+                    .map(|row| row.result.username.clone())
+                    .collect();
+                match checkpoint.pattern.as_deref().map(Pattern::parse) {
+                    Some(Ok(pattern)) => {
+                        self.run_usernames = None;
+                        self.run_skipped = Arc::default();
+                        self.run_pattern = checkpoint.pattern;
+                        let remaining = pattern
+                            .valid_names(self.rules.clone())
+                            .filter(move |u| !done.contains(u));
+                        self.launch(remaining, remaining_count)
+                    }
+                    Some(Err(e)) => {
+                        self.status_message = format!("Can't resume pattern: {}", e);
+                        Task::none()
+                    }
+                    None => {
+                        let list = UsernameList {
+                            file: checkpoint.file,
+                            ..UsernameList::from_words("the unfinished run", checkpoint.usernames)
+                        };
+                        let names = match self.list_usernames(&list) {
+                            Ok(names) => names,
+                            Err(e) => {
+                                self.status_message = format!("Can't resume list: {}", e);
+                                return Task::none();
+                            }
+                        };
+                        let skipped: Arc<HashSet<String>> =
+                            Arc::new(checkpoint.skipped.into_iter().collect());
+                        let remaining = {
+                            let skipped = Arc::clone(&skipped);
+                            names.filter(move |u| !done.contains(u) && !skipped.contains(u))
+                        };
+                        self.run_usernames = Some(list);
+                        self.run_skipped = skipped;
+                        self.run_pattern = None;
+                        self.launch(remaining, remaining_count)
+                    }
+                }
+            }
+            Message::DiscardCheckpoint => {
+                self.pending_checkpoint = None;
+                remove_checkpoint()
+            }
+        }
+    }
+
+    /// Fills the usernames editor from a list. Lists too long for the editor
+    /// are held aside and checked as they are.
+    fn import_usernames(&mut self, list: UsernameList) {
+        self.status_message = format!("{} usernames loaded from {}", list.len, list.name);
+        if list.file.is_some() || list.len > IMPORT_EDITOR_LIMIT {
+            self.usernames_content = text_editor::Content::new();
+            self.imported_usernames = Some(list);
+        } else {
+            self.usernames_content = text_editor::Content::with_text(&list.words.join("\n"));
+            self.imported_usernames = None;
+        }
+    }
+
+    fn import_proxies(&mut self, file: Wordlist) {
+        self.status_message = format!("{} proxies loaded from {}", file.words.len(), file.name);
+        self.proxies_content = text_editor::Content::with_text(&file.words.join("\n"));
+        self.show_proxy_panel = true;
+        self.save_settings();
+    }
+
+    /// Re-derives the wordlist candidates off the UI thread.
+    fn generate_mutations(&self) -> Task<Message> {
+        let Some(wordlist) = &self.wordlist else {
+            return Task::none();
+        };
+
+        let words = wordlist.words.clone();
+        let mutations = self.mutations.clone();
+        let rules = self.rules.clone();
+        Task::perform(
+            async move {
+                let mutated = tokio::task::spawn_blocking({
+                    let mutations = mutations.clone();
+                    move || mutate(&words, &mutations, &rules, MUTATION_LIMIT)
+                })
+                .await
+                .unwrap_or_default();
+                (mutations, Arc::new(mutated))
+            },
+            |(mutations, mutated)| Message::MutationsGenerated(mutations, mutated),
+        )
+    }
+
+    /// Starts a run over the valid names in a list. History is looked up off
+    /// the UI thread: first when recently taken names are skipped, otherwise
+    /// alongside the run, since it's then only shown next to the results.
+    fn start_list(&mut self, list: UsernameList, preflight: Preflight) -> Task<Message> {
+        self.last_known.clear();
+        if self.history.is_none() {
+            return self.launch_list(list, preflight);
+        }
+
+        let usernames = match self.list_usernames(&list) {
+            Ok(usernames) => usernames,
+            Err(e) => {
+                self.status_message = format!("Couldn't read usernames: {}", e);
+                return Task::none();
+            }
+        };
+        // Answers from the run about to start must not count as history.
+        let before = Utc::now();
+        if self.skip_taken_window().is_some() {
+            self.run_generation += 1;
+            let generation = self.run_generation;
+            self.is_checking = true;
+            self.status_message = format!("Looking up {} usernames in history...", preflight.valid);
+            let lookup = history::lookup(usernames, before);
+            return Task::perform(lookup, move |known| {
+                Message::HistoryLookedUp(generation, list, preflight, known)
+            });
+        }
+
+        let lookup = history::lookup(usernames, before);
+        let task = self.launch_list(list, preflight);
+        let generation = self.run_generation;
+        Task::batch([
+            task,
+            Task::perform(lookup, move |known| {
+                Message::LastKnownLoaded(generation, known)
+            }),
+        ])
+    }
+
+    fn skip_taken_window(&self) -> Option<chrono::Duration> {
+        match self.skip_taken_days.parse::<u32>() {
+            Ok(days) if days > 0 => Some(chrono::Duration::days(days.into())),
+            _ => None,
+        }
+    }
+
+    fn apply_last_known(&mut self, known: Result<HashMap<String, LastKnown>, String>) {
+        match known {
+            Ok(known) => self.last_known = known,
+            Err(e) => self.status_message = format!("History lookup failed: {}", e),
+        }
+    }
+
+    /// Starts a run over the valid names in a list, leaving out ones recently
+    /// seen taken when that's enabled. Lines the preflight rejected are
+    /// recorded as invalid up front.
+    fn launch_list(&mut self, list: UsernameList, preflight: Preflight) -> Task<Message> {
+        let skipped: HashSet<String> = match self.skip_taken_window() {
+            Some(window) => self
+                .last_known
+                .iter()
+                .filter(|(_, last)| history::is_fresh_taken(Some(last), window))
+                .map(|(username, _)| username.clone())
+                .collect(),
+            None => HashSet::new(),
+        };
+        let count = preflight.valid.saturating_sub(skipped.len());
+
+        if count == 0 {
+            self.status_message = format!(
+                "All {} usernames were taken within the last {} days",
+                preflight.valid, self.skip_taken_days
+            );
+            return Task::none();
+        }
+
+        let usernames = match self.list_usernames(&list) {
+            Ok(usernames) => usernames,
+            Err(e) => {
+                self.status_message = format!("Couldn't read usernames: {}", e);
+                return Task::none();
+            }
+        };
+        let skipped = Arc::new(skipped);
+        let usernames = {
+            let skipped = Arc::clone(&skipped);
+            usernames.filter(move |u| !skipped.contains(u))
+        };
+
+        self.results.clear();
+        self.stats = Stats {
+            total: count + preflight.rejected.len(),
+            ..Default::default()
+        };
+        self.pending_checkpoint = None;
+        self.run_usernames = Some(list);
+        self.run_skipped = skipped;
+        self.run_pattern = None;
+        self.run_started_at = Some(Local::now());
+        let task = self.launch(usernames, count);
+        if !self.is_checking {
+            return task;
+        }
+
+        self.status_message = format!("{} · {}", preflight.summary(), self.status_message);
+        if !self.run_skipped.is_empty() {
+            self.status_message = format!(
+                "{} (skipped {} recently taken)",
+                self.status_message,
+                self.run_skipped.len()
+            );
+        }
+
+        for rejected in &preflight.rejected {
+            let result = CheckResult {
+                username: rejected.input.clone(),
+                status: ResultStatus::Invalid,
+                attempts: 0,
+                latency: Duration::ZERO,
+                proxy: None,
+                checked_at: Utc::now(),
+            };
+            self.record(&result);
+            self.stats.add(&result.status);
+            self.stats.checked += 1;
+            self.results.push(ResultRow::new(result));
+        }
+        task
+    }
+
+    /// Writes a result to the auto-save files and history.
+    fn record(&mut self, result: &CheckResult) {
+        if let Some(Err(e)) = self.autosave.as_mut().map(|a| a.record(result)) {
+            self.status_message = format!("Auto-save failed: {}", e);
+            self.autosave = None;
+        }
+
+        if let (Some(recorder), Some(run_id)) = (&self.recorder, self.history_run)
+            && let Err(e) = recorder.record(run_id, result)
+        {
+            self.status_message = format!("History disabled: {}", e);
+            self.history = None;
+            self.recorder = None;
+        }
+    }
+
+    /// The valid names in `list`, each once. A file is read as the run goes.
+    fn list_usernames(
+        &self,
+        list: &UsernameList,
+    ) -> Result<impl Iterator<Item = String> + Send + use<>, String> {
+        let lines = list.lines()?.map_while(Result::ok);
+        Ok(preflight::usernames(lines, self.rules.clone()))
+    }
+
+    /// Starts a run over `usernames`; `count` is only used for the status line.
+    fn launch<I>(&mut self, usernames: I, count: usize) -> Task<Message>
+    where
+        I: IntoIterator<Item = String>,
+        I::IntoIter: Send + 'static,
+    {
+        match AutoSave::open(
+            self.hits_file.as_deref(),
+            self.errors_file.as_deref(),
+            self.ndjson_file.as_deref(),
+        ) {
+            Ok(autosave) => self.autosave = Some(autosave),
+            Err(e) => {
+                self.status_message = format!("Auto-save failed: {}", e);
+                return Task::none();
+            }
+        }
+
+        self.history_run = match &self.history {
+            Some(history) => history.start_run(history::Source::Gui).ok(),
+            None => None,
+        };
+
+        self.is_checking = true;
+        self.effective_concurrency = None;
+        self.run_finished_at = None;
+        self.last_checkpoint = None;
+        self.status_message = format!("Checking {} usernames...", count);
+
+        let threads = self.concurrency.parse().unwrap_or(5).max(1);
+        let concurrency = if self.adaptive_concurrency {
+            Concurrency::adaptive(threads)
+        } else {
+            Concurrency::Fixed(threads)
+        };
+
+        self.run_generation += 1;
+        let generation = self.run_generation;
+        let (task, cancel_handle) = self.run_checker(usernames, concurrency, move |event| {
+            Message::CheckEventReceived(generation, event)
+        });
+        self.cancel_handle = Some(cancel_handle);
+        task
+    }
+
+    /// Re-checks watched names in the background, alongside any regular run.
+    fn start_watch(&mut self, usernames: Vec<String>) -> Task<Message> {
+        if self.watch_cancel.is_some() || usernames.is_empty() {
+            return Task::none();
+        }
+
+        self.watch_run = match &self.history {
+            Some(history) => history.start_run(history::Source::Watch).ok(),
+            None => None,
+        };
+
+        let (task, cancel_handle) = self.run_checker(
+            usernames,
+            Concurrency::Fixed(WATCH_CONCURRENCY),
+            Message::WatchEventReceived,
+        );
+        self.watch_cancel = Some(cancel_handle);
+        task
+    }
+
+    /// Starts the checker with the current rate, retry and proxy settings.
+    fn run_checker<I>(
+        &mut self,
+        usernames: I,
+        concurrency: Concurrency,
+        on_event: impl Fn(CheckEvent) -> Message + Send + 'static,
+    ) -> (Task<Message>, CancelHandle)
+    where
+        I: IntoIterator<Item = String>,
+        I::IntoIter: Send + 'static,
+    {
+        let rate = RateLimit {
+            global_rps: self.rate_limit.parse().unwrap_or(0.0),
+            per_proxy_rps: if self.proxy_type != ProxyType::None {
+                self.proxy_rate_limit.parse().unwrap_or(0.0)
+            } else {
+                0.0
+            },
+        };
+        if self.rate_buckets.rate() != rate {
+            self.rate_buckets = RateBuckets::new(rate);
+        }
+        let retry = RetryPolicy {
+            max_attempts: self.max_attempts.parse().unwrap_or(5).max(1),
+            ..Default::default()
+        };
+
+        let proxies: Vec<String> = if self.proxy_type != ProxyType::None {
+            self.proxy_lines()
+                .iter()
+                .map(|s| self.proxy_type.format_proxy(s))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let backend =
+            Arc::new(JsonBackend::new(self.backend.clone()).with_rules(self.rules.clone()));
+        let (rx, cancel_handle) = check_usernames_stream_with_buckets(
+            usernames,
+            proxies,
+            self.rate_buckets.clone(),
+            concurrency,
+            retry,
+            backend,
+        );
+        (Task::run(rx, on_event), cancel_handle)
+    }
+
+    fn autosave_path(&mut self, target: AutoSaveTarget) -> &mut Option<PathBuf> {
+        match target {
+            AutoSaveTarget::Hits => &mut self.hits_file,
+            AutoSaveTarget::Errors => &mut self.errors_file,
+            AutoSaveTarget::Ndjson => &mut self.ndjson_file,
+        }
+    }
+
+    fn proxy_lines(&self) -> Vec<String> {
+        self.proxies_content
+            .text()
+            .lines()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect()
+    }
+
+    fn run_settings(&self) -> RunSettings {
+        RunSettings {
+            proxy_type: self.proxy_type,
+            proxies: self.proxy_lines(),
+            rate_limit: self.rate_limit.clone(),
+            proxy_rate_limit: self.proxy_rate_limit.clone(),
+            concurrency: self.concurrency.clone(),
+            adaptive_concurrency: self.adaptive_concurrency,
+            max_attempts: self.max_attempts.clone(),
+            skip_taken_days: self.skip_taken_days.clone(),
+        }
+    }
+
+    fn apply_settings(&mut self, settings: &RunSettings) {
+        self.proxy_type = settings.proxy_type;
+        self.proxies_content = text_editor::Content::with_text(&settings.proxies.join("\n"));
+        self.rate_limit = settings.rate_limit.clone();
+        self.proxy_rate_limit = settings.proxy_rate_limit.clone();
+        self.concurrency = settings.concurrency.clone();
+        self.adaptive_concurrency = settings.adaptive_concurrency;
+        self.max_attempts = settings.max_attempts.clone();
+        self.skip_taken_days = settings.skip_taken_days.clone();
+    }
+
+    /// Writes the settings file if anything changed since the last save.
+    fn save_settings(&mut self) {
+        let settings = Settings {
+            run: self.run_settings(),
+            last_tab: self.current_tab,
+            show_proxy_panel: self.show_proxy_panel,
+            window: self.window_size,
+        };
+        if settings == self.saved_settings {
+            return;
+        }
+
+        match settings::save(&settings) {
+            Ok(()) => self.saved_settings = settings,
+            Err(e) => self.status_message = format!("Couldn't save settings: {}", e),
+        }
+    }
+
+    /// Saves settings once edits have paused for [`SETTINGS_SAVE_DELAY`], so a
+    /// burst of keystrokes or resize events writes the file once.
+    fn save_settings_later(&mut self) -> Task<Message> {
+        self.settings_edits += 1;
+        let edit = self.settings_edits;
+        Task::perform(tokio::time::sleep(SETTINGS_SAVE_DELAY), move |()| {
+            Message::SettingsSaveDue(edit)
+        })
+    }
+
+    fn notify(&self, event: WebhookEvent) -> Task<Message> {
+        match &self.notifier {
+            Some(notifier) => {
+                let notifier = notifier.clone();
+                Task::perform(
+                    async move { notifier.notify(event).await },
+                    Message::WebhooksDelivered,
+                )
+            }
+            None => Task::none(),
+        }
+    }
+
+    fn save_watchlist(&mut self) {
+        if let Err(e) = watchlist::save(&self.watchlist) {
+            self.status_message = format!("Couldn't save watchlist: {}", e);
+        }
+    }
+
+    /// Whether a started run hasn't finished, so closing should checkpoint it.
+    fn has_resumable_run(&self) -> bool {
+        self.run_usernames.is_some() || self.run_pattern.is_some()
+    }
+
+    fn save_checkpoint(&mut self) -> Task<Message> {
+        if !self.has_resumable_run() {
+            return Task::none();
+        }
+
+        self.last_checkpoint = Some(Instant::now());
+        let checkpoint = Checkpoint {
+            saved_at: Local::now().format("%Y-%m-%d %H:%M").to_string(),
+            usernames: self
+                .run_usernames
+                .as_ref()
+                .map(|list| list.words.to_vec())
+                .unwrap_or_default(),
+            settings: self.run_settings(),
+            results: self.results.iter().map(|row| row.result.clone()).collect(),
+            pattern: self.run_pattern.clone(),
+            file: self
+                .run_usernames
+                .as_ref()
+                .and_then(|list| list.file.clone()),
+            skipped: self.run_skipped.iter().cloned().collect(),
+            total: self.run_usernames.is_some().then_some(self.stats.total),
+        };
+        Task::perform(checkpoint::save(checkpoint), Message::CheckpointSaved)
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        let watch = if self.watchlist.entries.is_empty() {
+            Subscription::none()
+        } else {
+            time::every(WATCH_TICK).map(|_| Message::WatchTick)
+        };
+
+        Subscription::batch([
+            window::resize_events().map(|(_, size)| Message::WindowResized(size)),
+            window::close_requests().map(|_| Message::CloseRequested),
+            window::events().filter_map(|(_, event)| match event {
+                window::Event::FileDropped(path) => Some(Message::FileDropped(path)),
+                _ => None,
+            }),
+            watch,
+        ])
+    }
+
+    pub fn view(&self) -> Element<'_, Message> {
+        let header = self.view_header();
+        let main_content = self.view_main();
+        let footer = self.view_footer();
+
+        let mut layout = column![header, space::vertical().height(16)];
+
+        if let Some(banner) = self.view_watch_alerts() {
+            layout = layout.push(banner).push(space::vertical().height(12));
+        }
+
+        if let Some(banner) = self.view_checkpoint_banner() {
+            layout = layout.push(banner).push(space::vertical().height(12));
+        }
+
+        let layout = layout
+            .push(main_content)
+            .push(space::vertical().height(12))
+            .push(footer)
+            .width(Fill)
+            .height(Fill);
+
+        let content = container(layout)
+            .style(|_| container::Style {
+                background: Some(Background::Color(theme::BG_DEEP)),
+                ..Default::default()
+            })
+            .width(Fill)
+            .height(Fill)
+            .padding(24);
+
+        if self.show_export_dialog {
+            ui::modal(
+                content,
+                self.view_export_dialog(),
+                Message::CloseExportDialog,
+            )
+        } else if self.show_watchlist {
+            ui::modal(content, self.view_watchlist(), Message::CloseWatchlist)
+        } else if self.show_generator {
+            ui::modal(content, self.view_generator(), Message::CloseGenerator)
+        } else if let Some((list, preflight)) = &self.preflight {
+            ui::modal(
+                content,
+                self.view_preflight(list, preflight),
+                Message::CancelPreflight,
+            )
+        } else {
+            content.into()
+        }
+    }
+
+    fn view_watch_alerts(&self) -> Option<Element<'_, Message>> {
+        if self.watch_alerts.is_empty() {
+            return None;
+        }
+
+        let banner = container(
+            row![
+                text("★").size(14).color(theme::SUCCESS),
+                space::horizontal().width(10),
+                text(self.watch_alerts.join(" · "))
+                    .size(12)
+                    .color(theme::TEXT_PRIMARY),
+                space::horizontal().width(Fill),
+                ui::secondary_button("Dismiss", true).on_press(Message::DismissWatchAlerts),
+            ]
+            .align_y(Alignment::Center),
+        )
+        .width(Fill)
+        .padding([10, 16])
+        .style(|_| container::Style {
+            background: Some(Background::Color(theme::BG_CARD)),
+            border: Border {
+                color: theme::SUCCESS.scale_alpha(0.4),
+                width: 1.0,
+                radius: Radius::new(10),
+            },
+            ..Default::default()
+        });
+
+        Some(banner.into())
+    }
+
+    fn view_watchlist(&self) -> Element<'_, Message> {
+        let add_row = row![
+            text_input("username", &self.watch_input)
+                .on_input(Message::WatchInputChanged)
+                .on_submit(Message::AddToWatchlist)
+                .padding([8, 10])
+                .size(12)
+                .style(|_, _| ui::input_style()),
+            ui::toggle_chip("Add", false).on_press(Message::AddToWatchlist),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center);
+
+        let interval_row = row![
+            text("Re-check every").size(11).color(theme::TEXT_MUTED),
+            space::horizontal().width(6),
+            text_input("60", &self.watch_interval)
+                .on_input(Message::WatchIntervalChanged)
+                .padding([8, 10])
+                .size(12)
+                .width(55)
+                .style(|_, _| ui::input_style()),
+            space::horizontal().width(4),
+            text("minutes").size(10).color(theme::TEXT_MUTED),
+        ]
+        .align_y(Alignment::Center);
+
+        let entries: Element<'_, Message> = if self.watchlist.entries.is_empty() {
+            text("Add usernames you want to grab once they're released")
+                .size(11)
+                .color(theme::TEXT_MUTED)
+                .into()
+        } else {
+            let rows = self.watchlist.entries.iter().map(|entry| {
+                let (status, color) = match &entry.status {
+                    Some(ResultStatus::Available) => ("available", theme::SUCCESS),
+                    Some(ResultStatus::Taken) => ("taken", theme::DANGER),
+                    _ => ("pending", theme::TEXT_MUTED),
+                };
+                let seen = match (entry.released_at, entry.last_checked) {
+                    (Some(at), _) => format!(
+                        "released {}",
+                        at.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+                    ),
+                    (None, Some(at)) => format!(
+                        "checked {}",
+                        at.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+                    ),
+                    (None, None) => "not checked yet".to_string(),
+                };
+
+                row![
+                    text(&entry.username).size(12).color(theme::TEXT_PRIMARY),
+                    space::horizontal().width(10),
+                    text(status).size(10).color(color),
+                    space::horizontal().width(Fill),
+                    text(seen).size(10).color(theme::TEXT_MUTED),
+                    space::horizontal().width(10),
+                    ui::toggle_chip("Remove", false)
+                        .on_press(Message::RemoveFromWatchlist(entry.username.clone())),
+                ]
+                .align_y(Alignment::Center)
+                .into()
+            });
+
+            scrollable(Column::with_children(rows).spacing(6))
+                .height(Length::Shrink)
+                .into()
+        };
+
+        let running = self.watch_cancel.is_some();
+        let can_check = !running && !self.watchlist.entries.is_empty();
+        let actions = row![
+            text(if running { "Checking..." } else { "" })
+                .size(11)
+                .color(theme::TEXT_SECONDARY),
+            space::horizontal().width(Fill),
+            ui::secondary_button("Close", true).on_press(Message::CloseWatchlist),
+            ui::action_button("Check now", theme::ACCENT_BLUE, can_check)
+                .on_press_maybe(can_check.then_some(Message::CheckWatchlistNow)),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center);
+
+        container(column![
+            text("Watchlist").size(14).color(theme::TEXT_BRIGHT),
+            space::vertical().height(4),
+            text("Names are re-checked in the background; a banner appears when one is released")
+                .size(10)
+                .color(theme::TEXT_MUTED),
+            space::vertical().height(16),
+            add_row,
+            space::vertical().height(10),
+            interval_row,
+            space::vertical().height(16),
+            container(entries).max_height(280),
+            space::vertical().height(20),
+            actions,
+        ])
+        .width(520)
+        .padding(22)
+        .style(|_| container::Style {
+            background: Some(Background::Color(theme::BG_CARD)),
+            border: Border {
+                color: theme::BORDER_SUBTLE,
+                width: 1.0,
+                radius: Radius::new(14),
+            },
+            ..Default::default()
+        })
+        .into()
+    }
+
+    fn view_generator(&self) -> Element<'_, Message> {
+        let modes = row![
+            ui::toggle_chip("Pattern", self.generator_mode == GeneratorMode::Pattern)
+                .on_press(Message::GeneratorModeChanged(GeneratorMode::Pattern)),
+            ui::toggle_chip("Wordlist", self.generator_mode == GeneratorMode::Wordlist)
+                .on_press(Message::GeneratorModeChanged(GeneratorMode::Wordlist)),
+        ]
+        .spacing(6);
+
+        let (body, valid, preview): (Element<'_, Message>, u128, &[String]) =
+            match self.generator_mode {
+                GeneratorMode::Pattern => (
+                    self.view_pattern_input(),
+                    self.generator_valid,
+                    &self.generator_preview,
+                ),
+                GeneratorMode::Wordlist => {
+                    let names = self.mutated.as_ref().map_or(&[][..], |m| &m.names);
+                    (
+                        self.view_mutation_options(),
+                        names.len() as u128,
+                        &names[..names.len().min(GENERATOR_PREVIEW)],
+                    )
+                }
+            };
+
+        let mut preview = preview.join("  ");
+        if valid > GENERATOR_PREVIEW as u128 {
+            preview.push_str("  …");
+        }
+        let can_insert = valid > 0 && valid <= GENERATOR_INSERT_LIMIT;
+        let can_check = valid > 0 && !self.is_checking;
+        let actions = row![
+            text(if valid > GENERATOR_INSERT_LIMIT {
+                "Too many to add to the list; check them directly"
+            } else {
+                ""
+            })
+            .size(10)
+            .color(theme::TEXT_MUTED),
+            space::horizontal().width(Fill),
+            ui::secondary_button("Close", true).on_press(Message::CloseGenerator),
+            ui::secondary_button("Add to list", can_insert)
+                .on_press_maybe(can_insert.then_some(Message::InsertGenerated)),
+            ui::action_button("Check now", theme::ACCENT_BLUE, can_check)
+                .on_press_maybe(can_check.then_some(Message::CheckGenerated)),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center);
+
+        container(column![
+            text("Generator").size(14).color(theme::TEXT_BRIGHT),
+            space::vertical().height(4),
+            text("Build candidates from a pattern, or from a wordlist and mutations")
+                .size(10)
+                .color(theme::TEXT_MUTED),
+            space::vertical().height(16),
+            modes,
+            space::vertical().height(12),
+            body,
+            space::vertical().height(10),
+            text(preview).size(11).color(theme::TEXT_PRIMARY),
+            space::vertical().height(20),
+            actions,
+        ])
+        .width(560)
+        .padding(22)
+        .style(|_| container::Style {
+            background: Some(Background::Color(theme::BG_CARD)),
+            border: Border {
+                color: theme::BORDER_SUBTLE,
+                width: 1.0,
+                radius: Radius::new(14),
+            },
+            ..Default::default()
+        })
+        .into()
+    }
+
+    fn view_pattern_input(&self) -> Element<'_, Message> {
+        let input = text_input("\\c\\v\\c<0-9>", &self.generator_input)
+            .on_input(Message::GeneratorInputChanged)
+            .padding([8, 10])
+            .size(12)
+            .style(|_, _| ui::input_style());
+
+        let valid = match &self.generator {
+            Some(Ok(pattern)) if !pattern.is_count_exact(&self.rules) => {
+                format!("≤ {}", self.generator_valid)
+            }
+            _ => self.generator_valid.to_string(),
+        };
+        let (summary, color) = match &self.generator {
+            None => (PATTERN_HELP.to_string(), theme::TEXT_MUTED),
+            Some(Err(e)) => (e.to_string(), theme::DANGER),
+            Some(Ok(pattern)) => (
+                format!(
+                    "{} candidates, {} valid usernames; names are produced as they're checked",
+                    pattern.count(),
+                    valid
+                ),
+                theme::TEXT_SECONDARY,
+            ),
+        };
+
+        column![
+            input,
+            space::vertical().height(10),
+            text(summary).size(11).color(color),
+        ]
+        .into()
+    }
+
+    fn view_mutation_options(&self) -> Element<'_, Message> {
+        let source = row![
+            ui::toggle_chip("Choose file...", false).on_press(Message::ChooseWordlist),
+            space::horizontal().width(10),
+            match &self.wordlist {
+                Some(wordlist) => text(format!(
+                    "{} · {} words",
+                    wordlist.name,
+                    wordlist.words.len()
+                ))
+                .size(11)
+                .color(theme::TEXT_PRIMARY),
+                None => text("One seed word per line")
+                    .size(11)
+                    .color(theme::TEXT_MUTED),
+            },
+        ]
+        .align_y(Alignment::Center);
+
+        let mutations = row(Mutation::ALL.iter().map(|mutation| {
+            ui::toggle_chip(mutation.to_string(), self.mutations.contains(mutation))
+                .on_press(Message::MutationToggled(*mutation))
+                .into()
+        }))
+        .spacing(6)
+        .wrap();
+
+        let summary = match &self.mutated {
+            Some(mutated) => {
+                let mut parts = vec![format!("{} seed words", mutated.seeds)];
+                parts.extend(
+                    mutated
+                        .added
+                        .iter()
+                        .map(|(mutation, count)| format!("+{} {}", count, mutation)),
+                );
+                let mut summary = format!(
+                    "{} → {} unique valid usernames",
+                    parts.join(" · "),
+                    mutated.names.len()
+                );
+                if mutated.truncated {
+                    summary.push_str(&format!(" (stopped at {})", MUTATION_LIMIT));
+                }
+                summary
             }
-            Message::ExportResults => {
-                let available: Vec<String> = self
-                    .results
-                    .iter()
-                    .filter(|r| r.status == ResultStatus::Available)
-                    .map(|r| r.username.clone())
-                    .collect();
+            None if self.wordlist.is_some() => "Generating...".to_string(),
+            None => String::new(),
+        };
 
-                if available.is_empty() {
-                    self.status_message = "No available usernames".to_string();
-                    return Task::none();
-                }
+        column![
+            source,
+            space::vertical().height(10),
+            mutations,
+            space::vertical().height(10),
+            text(summary).size(11).color(theme::TEXT_SECONDARY),
+        ]
+        .into()
+    }
 
-                Task::perform(export_to_file(available), Message::ExportComplete)
-            }
-            Message::ExportComplete(result) => {
-                self.status_message = match result {
-                    Ok(path) => format!("Saved: {}", path),
-                    Err(e) => e,
-                };
-                Task::none()
-            }
-            Message::ClearResults => {
-                self.results.clear();
-                self.stats = Stats::default();
-                self.status_message.clear();
-                Task::none()
-            }
+    fn view_preflight<'a>(
+        &self,
+        usernames: &UsernameList,
+        preflight: &'a Preflight,
+    ) -> Element<'a, Message> {
+        let rows = preflight
+            .rejected
+            .iter()
+            .take(PREFLIGHT_REJECTED_SHOWN)
+            .map(|rejected| {
+                row![
+                    text(format!("line {}", rejected.line))
+                        .size(10)
+                        .color(theme::TEXT_MUTED)
+                        .width(70),
+                    text(&rejected.input).size(12).color(theme::TEXT_PRIMARY),
+                    space::horizontal().width(Fill),
+                    text(rejected.reason.to_string())
+                        .size(10)
+                        .color(theme::WARNING),
+                ]
+                .align_y(Alignment::Center)
+                .into()
+            });
+        let mut list = Column::with_children(rows).spacing(6);
+        if preflight.rejected.len() > PREFLIGHT_REJECTED_SHOWN {
+            list = list.push(
+                text(format!(
+                    "and {} more",
+                    preflight.rejected.len() - PREFLIGHT_REJECTED_SHOWN
+                ))
+                .size(10)
+                .color(theme::TEXT_MUTED),
+            );
         }
-    }
 
-    pub fn view(&self) -> Element<'_, Message> {
-        let header = self.view_header();
-        let main_content = self.view_main();
-        let footer = self.view_footer();
+        let can_start = preflight.valid > 0 && !self.is_checking;
+        // A file stays as it is on disk; the run leaves out the same lines.
+        let can_clean_up = usernames.file.is_none();
+        let actions = row![
+            space::horizontal().width(Fill),
+            ui::secondary_button("Cancel", true).on_press(Message::CancelPreflight),
+            ui::secondary_button("Clean up list", can_clean_up)
+                .on_press_maybe(can_clean_up.then_some(Message::ApplyPreflight)),
+            ui::action_button("Start", theme::ACCENT_BLUE, can_start)
+                .on_press_maybe(can_start.then_some(Message::ConfirmPreflight)),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center);
 
-        let layout = column![
-            header,
+        container(column![
+            text("Check List").size(14).color(theme::TEXT_BRIGHT),
+            space::vertical().height(4),
+            text(preflight.summary())
+                .size(11)
+                .color(theme::TEXT_SECONDARY),
+            space::vertical().height(4),
+            text("Lowercased and deduplicated; invalid lines are listed as Invalid")
+                .size(10)
+                .color(theme::TEXT_MUTED),
             space::vertical().height(16),
-            main_content,
-            space::vertical().height(12),
-            footer,
-        ]
-        .width(Fill)
-        .height(Fill);
+            container(scrollable(list).height(Length::Shrink)).max_height(280),
+            space::vertical().height(20),
+            actions,
+        ])
+        .width(560)
+        .padding(22)
+        .style(|_| container::Style {
+            background: Some(Background::Color(theme::BG_CARD)),
+            border: Border {
+                color: theme::BORDER_SUBTLE,
+                width: 1.0,
+                radius: Radius::new(14),
+            },
+            ..Default::default()
+        })
+        .into()
+    }
 
-        container(layout)
-            .style(|_| container::Style {
-                background: Some(Background::Color(theme::BG_DEEP)),
-                ..Default::default()
-            })
-            .width(Fill)
-            .height(Fill)
-            .padding(24)
+    fn view_export_dialog(&self) -> Element<'_, Message> {
+        let count = self
+            .results
+            .iter()
+            .filter(|row| self.export_options.includes(&row.result.status))
+            .count();
+
+        let categories = row(ResultCategory::ALL.iter().map(|category| {
+            ui::toggle_chip(
+                category.to_string(),
+                self.export_options.categories.contains(category),
+            )
+            .on_press(Message::ExportCategoryToggled(*category))
             .into()
+        }))
+        .spacing(6);
+
+        let formats = row(ExportFormat::ALL.iter().map(|format| {
+            ui::toggle_chip(format.to_string(), self.export_options.format == *format)
+                .on_press(Message::ExportFormatChanged(*format))
+                .into()
+        }))
+        .spacing(6);
+
+        let hint = text(match self.export_options.format {
+            ExportFormat::Text => "One username per line",
+            ExportFormat::Csv => "Status, error, proxy, attempts and latency per username",
+            ExportFormat::Json => "Results with run settings, timing and totals",
+        })
+        .size(10)
+        .color(theme::TEXT_MUTED);
+
+        let actions = row![
+            text(format!("{} usernames", count))
+                .size(11)
+                .color(theme::TEXT_SECONDARY),
+            space::horizontal().width(Fill),
+            ui::secondary_button("Cancel", true).on_press(Message::CloseExportDialog),
+            ui::action_button("Save", theme::ACCENT_BLUE, count > 0)
+                .on_press_maybe((count > 0).then_some(Message::ExportResults)),
+        ]
+        .spacing(10)
+        .align_y(Alignment::Center);
+
+        container(column![
+            text("Export Results").size(14).color(theme::TEXT_BRIGHT),
+            space::vertical().height(16),
+            text("Include").size(11).color(theme::TEXT_MUTED),
+            space::vertical().height(8),
+            categories,
+            space::vertical().height(16),
+            text("Format").size(11).color(theme::TEXT_MUTED),
+            space::vertical().height(8),
+            formats,
+            space::vertical().height(6),
+            hint,
+            space::vertical().height(20),
+            actions,
+        ])
+        .width(420)
+        .padding(22)
+        .style(|_| container::Style {
+            background: Some(Background::Color(theme::BG_CARD)),
+            border: Border {
+                color: theme::BORDER_SUBTLE,
+                width: 1.0,
+                radius: Radius::new(14),
+            },
+            ..Default::default()
+        })
+        .into()
     }
 
     fn view_header(&self) -> Element<'_, Message> {
@@ -245,16 +2169,68 @@ impl App {
         ]
         .spacing(8);
 
+        let watchlist_btn = ui::toggle_chip(
+            format!("Watchlist {}", self.watchlist.entries.len()),
+            self.watch_cancel.is_some(),
+        )
+        .on_press(Message::OpenWatchlist);
+
         row![
             space::horizontal().width(14),
             title_area,
             space::horizontal().width(Fill),
+            watchlist_btn,
+            space::horizontal().width(12),
             stats_pills,
         ]
         .align_y(Alignment::Center)
         .into()
     }
 
+    fn view_checkpoint_banner(&self) -> Option<Element<'_, Message>> {
+        let checkpoint = self.pending_checkpoint.as_ref()?;
+
+        let summary = text(format!(
+            "Unfinished run from {}: {} of {} usernames checked",
+            checkpoint.saved_at,
+            checkpoint.definitive_count(),
+            self.pending_total
+        ))
+        .size(12)
+        .color(theme::TEXT_PRIMARY);
+
+        let banner = container(
+            row![
+                text("↻").size(14).color(theme::ACCENT_CYAN),
+                space::horizontal().width(10),
+                summary,
+                space::horizontal().width(Fill),
+                ui::action_button("Resume", theme::ACCENT_PURPLE, !self.is_checking)
+                    .on_press_maybe(if !self.is_checking {
+                        Some(Message::ResumeCheckpoint)
+                    } else {
+                        None
+                    }),
+                space::horizontal().width(8),
+                ui::secondary_button("Discard", true).on_press(Message::DiscardCheckpoint),
+            ]
+            .align_y(Alignment::Center),
+        )
+        .width(Fill)
+        .padding([10, 16])
+        .style(|_| container::Style {
+            background: Some(Background::Color(theme::BG_CARD)),
+            border: Border {
+                color: theme::ACCENT_CYAN.scale_alpha(0.35),
+                width: 1.0,
+                radius: Radius::new(10),
+            },
+            ..Default::default()
+        });
+
+        Some(banner.into())
+    }
+
     fn view_main(&self) -> Element<'_, Message> {
         let left_panel = self.view_input_panel();
         let right_panel = self.view_results_panel();
@@ -273,18 +2249,50 @@ impl App {
             text("Usernames").size(12).color(theme::TEXT_PRIMARY),
             space::horizontal().width(Fill),
             text("one per line").size(10).color(theme::TEXT_MUTED),
-        ];
+            space::horizontal().width(10),
+            ui::toggle_chip("Import", false).on_press(Message::ImportFile(ImportTarget::Usernames)),
+            space::horizontal().width(6),
+            ui::toggle_chip("Generate", false).on_press(Message::OpenGenerator),
+        ]
+        .align_y(Alignment::Center);
 
-        let username_editor = text_editor(&self.usernames_content)
-            .placeholder("dream\nnotch\njeb_\n...")
-            .on_action(Message::UsernamesChanged)
+        let editor_height = if self.show_proxy_panel {
+            Length::Fixed(140.0)
+        } else {
+            Fill
+        };
+        let username_editor: Element<'_, Message> = match &self.imported_usernames {
+            Some(file) => container(
+                row![
+                    text(format!("{} usernames loaded from {}", file.len, file.name))
+                        .size(12)
+                        .color(theme::TEXT_PRIMARY),
+                    space::horizontal().width(Fill),
+                    ui::toggle_chip("Clear", false).on_press(Message::ClearImport),
+                ]
+                .align_y(Alignment::Center),
+            )
             .padding(14)
-            .height(if self.show_proxy_panel {
-                Length::Fixed(140.0)
-            } else {
-                Fill
+            .width(Fill)
+            .height(editor_height)
+            .style(|_| container::Style {
+                background: Some(Background::Color(theme::BG_INPUT)),
+                border: Border {
+                    color: theme::BORDER_SUBTLE,
+                    width: 1.0,
+                    radius: Radius::new(10),
+                },
+                ..Default::default()
             })
-            .style(|_, _| ui::editor_style());
+            .into(),
+            None => text_editor(&self.usernames_content)
+                .placeholder("dream\nnotch\njeb_\n...")
+                .on_action(Message::UsernamesChanged)
+                .padding(14)
+                .height(editor_height)
+                .style(|_, _| ui::editor_style())
+                .into(),
+        };
 
         let mut content = column![
             username_header,
@@ -377,10 +2385,23 @@ impl App {
             },
         });
 
+        let proxy_rate_input = text_input("∞", &self.proxy_rate_limit)
+            .on_input(Message::ProxyRateChanged)
+            .padding([8, 10])
+            .size(12)
+            .width(55)
+            .style(|_, _| ui::input_style());
+
         let type_row = row![
             text("Type").size(11).color(theme::TEXT_MUTED),
             space::horizontal().width(12),
             type_picker,
+            space::horizontal().width(Fill),
+            text("Per proxy").size(11).color(theme::TEXT_MUTED),
+            space::horizontal().width(6),
+            proxy_rate_input,
+            space::horizontal().width(4),
+            text("req/s").size(10).color(theme::TEXT_MUTED),
         ]
         .align_y(Alignment::Center);
 
@@ -394,13 +2415,19 @@ impl App {
         let hint = text("Proxies rotate automatically. Format: host:port or user:pass@host:port")
             .size(10)
             .color(theme::TEXT_MUTED);
+        let hint_row = row![
+            hint,
+            space::horizontal().width(Fill),
+            ui::toggle_chip("Import", false).on_press(Message::ImportFile(ImportTarget::Proxies)),
+        ]
+        .align_y(Alignment::Center);
 
         container(column![
             type_row,
             space::vertical().height(10),
             proxy_editor,
             space::vertical().height(6),
-            hint,
+            hint_row,
         ])
         .width(Fill)
         .padding(14)
@@ -417,11 +2444,11 @@ impl App {
     }
 
     fn view_settings_row(&self) -> Element<'_, Message> {
-        let delay_input = text_input("100", &self.delay_ms)
-            .on_input(Message::DelayChanged)
+        let rate_input = text_input("∞", &self.rate_limit)
+            .on_input(Message::RateChanged)
             .padding([8, 10])
             .size(12)
-            .width(65)
+            .width(55)
             .style(|_, _| ui::input_style());
 
         let threads_input = text_input("5", &self.concurrency)
@@ -431,20 +2458,97 @@ impl App {
             .width(50)
             .style(|_, _| ui::input_style());
 
-        row![
-            text("Delay").size(11).color(theme::TEXT_MUTED),
+        let attempts_input = text_input("5", &self.max_attempts)
+            .on_input(Message::MaxAttemptsChanged)
+            .padding([8, 10])
+            .size(12)
+            .width(50)
+            .style(|_, _| ui::input_style());
+
+        let pacing_row = row![
+            text("Rate").size(11).color(theme::TEXT_MUTED),
             space::horizontal().width(6),
-            delay_input,
-            text("ms").size(10).color(theme::TEXT_MUTED),
+            rate_input,
+            space::horizontal().width(4),
+            text("req/s").size(10).color(theme::TEXT_MUTED),
             space::horizontal().width(Fill),
+            text("Attempts").size(11).color(theme::TEXT_MUTED),
+            space::horizontal().width(6),
+            attempts_input,
+        ]
+        .align_y(Alignment::Center);
+
+        let skip_input = text_input("off", &self.skip_taken_days)
+            .on_input(Message::SkipTakenDaysChanged)
+            .padding([8, 10])
+            .size(12)
+            .width(50)
+            .style(|_, _| ui::input_style());
+
+        let threads_row = row![
             text("Threads").size(11).color(theme::TEXT_MUTED),
             space::horizontal().width(6),
             threads_input,
+            space::horizontal().width(6),
+            ui::toggle_chip("Auto", self.adaptive_concurrency)
+                .on_press(Message::ToggleAdaptiveConcurrency),
+            space::horizontal().width(Fill),
+            text("Skip taken within").size(11).color(theme::TEXT_MUTED),
+            space::horizontal().width(6),
+            skip_input,
+            space::horizontal().width(4),
+            text("days").size(10).color(theme::TEXT_MUTED),
         ]
-        .align_y(Alignment::Center)
+        .align_y(Alignment::Center);
+
+        column![
+            pacing_row,
+            threads_row,
+            self.view_autosave_row("Save hits", AutoSaveTarget::Hits, &self.hits_file),
+            self.view_autosave_row("Save errors", AutoSaveTarget::Errors, &self.errors_file),
+            self.view_autosave_row("Log NDJSON", AutoSaveTarget::Ndjson, &self.ndjson_file),
+        ]
+        .spacing(8)
         .into()
     }
 
+    fn view_autosave_row<'a>(
+        &self,
+        label: &'a str,
+        target: AutoSaveTarget,
+        path: &'a Option<PathBuf>,
+    ) -> Element<'a, Message> {
+        let file_name = match path {
+            Some(path) => text(
+                path.file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_else(|| path.display().to_string()),
+            )
+            .size(11)
+            .color(theme::TEXT_PRIMARY),
+            None => text("off").size(11).color(theme::TEXT_MUTED),
+        };
+
+        let mut content = row![
+            text(label).size(11).color(theme::TEXT_MUTED),
+            space::horizontal().width(8),
+            file_name,
+            space::horizontal().width(Fill),
+        ]
+        .align_y(Alignment::Center);
+
+        let editable = !self.is_checking;
+        if path.is_some() {
+            let clear = ui::toggle_chip("Off", false)
+                .on_press_maybe(editable.then_some(Message::ClearAutoSave(target)));
+            content = content.push(clear).push(space::horizontal().width(6));
+        }
+
+        let choose = ui::toggle_chip("Choose...", false)
+            .on_press_maybe(editable.then_some(Message::ChooseAutoSave(target)));
+        content.push(choose).into()
+    }
+
     fn view_results_panel(&self) -> Element<'_, Message> {
         let tabs = row![
             glow_tab("All", Tab::All, self.current_tab, self.results.len()),
@@ -465,23 +2569,34 @@ impl App {
             0.0
         };
 
-        let progress_text = text(format!("{}/{}", self.stats.checked, self.stats.total))
-            .size(11)
-            .color(theme::TEXT_MUTED);
+        let progress_label = match self.effective_concurrency {
+            Some(limit) if self.adaptive_concurrency && self.is_checking => format!(
+                "{}/{} · {} threads",
+                self.stats.checked, self.stats.total, limit
+            ),
+            _ => format!("{}/{}", self.stats.checked, self.stats.total),
+        };
+
+        let progress_text = text(progress_label).size(11).color(theme::TEXT_MUTED);
 
         let header_row =
             row![tabs, space::horizontal().width(Fill), progress_text].align_y(Alignment::Center);
 
         let progress_bar = self.view_progress_bar(progress);
 
-        let filtered: Vec<_> = self
+        let mut filtered: Vec<_> = self
             .results
             .iter()
-            .filter(|r| match self.current_tab {
+            .filter(|row| match self.current_tab {
                 Tab::All => true,
-                Tab::Available => r.status == ResultStatus::Available,
-                Tab::Taken => r.status == ResultStatus::Taken,
-                Tab::Errors => matches!(r.status, ResultStatus::Error(_) | ResultStatus::Invalid),
+                Tab::Available => row.result.status == ResultStatus::Available,
+                Tab::Taken => row.result.status == ResultStatus::Taken,
+                Tab::Errors => match self.error_filter {
+                    Some(kind) => {
+                        matches!(&row.result.status, ResultStatus::Error(e) if e.kind == kind)
+                    }
+                    None => !row.result.status.is_definitive(),
+                },
             })
             .collect();
 
@@ -515,8 +2630,18 @@ impl App {
             .center(Fill)
             .into()
         } else {
-            let items: Vec<Element<'_, Message>> =
-                filtered.iter().map(|r| result_row(r)).collect();
+            let show_score = self.current_tab == Tab::Available;
+            if show_score && self.sort_by_score {
+                filtered.sort_by_key(|row| std::cmp::Reverse(row.score));
+            }
+
+            let items: Vec<Element<'_, Message>> = filtered
+                .into_iter()
+                .map(|row| {
+                    let last_known = self.last_known.get(&row.result.username);
+                    result_row(&row.result, row.score.filter(|_| show_score), last_known)
+                })
+                .collect();
 
             scrollable(Column::with_children(items).spacing(4).padding(8))
                 .height(Fill)
@@ -535,17 +2660,69 @@ impl App {
                 ..Default::default()
             });
 
-        ui::glass_card(column![
-            header_row,
-            space::vertical().height(12),
-            progress_bar,
-            space::vertical().height(12),
-            results_box,
-        ])
+        let mut content = column![header_row, space::vertical().height(12), progress_bar];
+
+        if self.current_tab == Tab::Errors {
+            content = content
+                .push(space::vertical().height(10))
+                .push(self.view_error_filters());
+        } else if self.current_tab == Tab::Available {
+            content = content
+                .push(space::vertical().height(10))
+                .push(self.view_score_sort());
+        }
+
+        ui::glass_card(content.push(space::vertical().height(12)).push(results_box))
+    }
+
+    fn view_error_filters(&self) -> Element<'_, Message> {
+        let mut counts: HashMap<ErrorKind, usize> = HashMap::new();
+        for row in &self.results {
+            if let ResultStatus::Error(e) = &row.result.status {
+                *counts.entry(e.kind).or_default() += 1;
+            }
+        }
+
+        let chips = ErrorKind::ALL
+            .iter()
+            .filter_map(|kind| counts.get(kind).map(|count| (*kind, *count)))
+            .map(|(kind, count)| {
+                ui::toggle_chip(
+                    format!("{} {}", kind, count),
+                    self.error_filter == Some(kind),
+                )
+                .on_press(Message::ErrorFilterChanged(
+                    (self.error_filter != Some(kind)).then_some(kind),
+                ))
+                .into()
+            });
+
+        row![ui::toggle_chip("All", self.error_filter.is_none())
+            .on_press(Message::ErrorFilterChanged(None))]
+        .extend(chips)
+        .spacing(6)
+        .wrap()
+        .into()
+    }
+
+    fn view_score_sort(&self) -> Element<'_, Message> {
+        row![
+            ui::toggle_chip("Found order", !self.sort_by_score)
+                .on_press(Message::SortByScoreChanged(false)),
+            ui::toggle_chip("Best score", self.sort_by_score)
+                .on_press(Message::SortByScoreChanged(true)),
+            space::horizontal().width(Fill),
+            text("Score: length, pronounceability, dictionary words, symbols, repeats")
+                .size(10)
+                .color(theme::TEXT_MUTED),
+        ]
+        .spacing(6)
+        .align_y(Alignment::Center)
+        .into()
     }
 
     fn view_progress_bar(&self, progress: f32) -> Element<'_, Message> {
-        let bar_width = (progress * 100.0).max(0.0).min(100.0);
+        let bar_width = (progress * 100.0).clamp(0.0, 100.0);
 
         let filled = container(space::horizontal())
             .width(Length::FillPortion(bar_width as u16))
@@ -579,7 +2756,9 @@ impl App {
 
     fn view_footer(&self) -> Element<'_, Message> {
         let start_btn = ui::action_button(
-            if self.is_checking {
+            if self.is_paused {
+                "Paused"
+            } else if self.is_checking {
                 "Checking..."
             } else {
                 "Start Check"
@@ -602,17 +2781,36 @@ impl App {
                 },
             );
 
-        let export_btn = ui::action_button(
-            "Export",
-            theme::ACCENT_BLUE,
-            self.stats.available > 0 && !self.is_checking,
+        let pause_btn = ui::action_button(
+            if self.is_paused { "Resume" } else { "Pause" },
+            theme::WARNING,
+            self.is_checking,
         )
-        .on_press_maybe(if self.stats.available > 0 && !self.is_checking {
-            Some(Message::ExportResults)
-        } else {
-            None
+        .on_press_maybe(match (self.is_checking, self.is_paused) {
+            (true, false) => Some(Message::PauseCheck),
+            (true, true) => Some(Message::ResumeCheck),
+            _ => None,
         });
 
+        let can_export = !self.results.is_empty() && !self.is_checking;
+        let export_btn = ui::action_button("Export", theme::ACCENT_BLUE, can_export)
+            .on_press_maybe(if can_export {
+                Some(Message::OpenExportDialog)
+            } else {
+                None
+            });
+
+        let has_errors = self
+            .results
+            .iter()
+            .any(|row| row.result.status.is_retryable());
+        let retry_btn = ui::secondary_button("Retry errors", has_errors && !self.is_checking)
+            .on_press_maybe(if has_errors && !self.is_checking {
+                Some(Message::RetryErrors)
+            } else {
+                None
+            });
+
         let clear_btn =
             ui::secondary_button("Clear", !self.results.is_empty() && !self.is_checking)
                 .on_press_maybe(if !self.results.is_empty() && !self.is_checking {
@@ -627,10 +2825,12 @@ impl App {
 
         row![
             start_btn,
+            pause_btn,
             stop_btn,
             space::horizontal().width(20),
             status,
             space::horizontal().width(Fill),
+            retry_btn,
             export_btn,
             clear_btn,
         ]
@@ -640,6 +2840,35 @@ impl App {
     }
 }
 
+fn remove_checkpoint() -> Task<Message> {
+    Task::future(checkpoint::remove()).discard()
+}
+
+/// Runs the preflight over `list` off the UI thread, reading a file line by
+/// line rather than all at once.
+async fn preflight_list(list: UsernameList, rules: UsernameRules) -> Result<Preflight, String> {
+    tokio::task::spawn_blocking(move || {
+        let mut error = None;
+        let lines = list
+            .lines()?
+            .map_while(|line| line.map_err(|e| error = Some(e)).ok());
+        let preflight = Preflight::run(lines, &rules);
+        match error {
+            Some(e) => Err(e.to_string()),
+            None => Ok(preflight),
+        }
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+fn is_valid_rate(value: &str) -> bool {
+    value.is_empty()
+        || value
+            .parse::<f64>()
+            .is_ok_and(|r| r.is_finite() && r >= 0.0)
+}
+
 fn glow_tab<'a>(label: &'a str, tab: Tab, current: Tab, count: usize) -> Element<'a, Message> {
     let is_active = tab == current;
     let accent = match tab {
@@ -701,11 +2930,17 @@ fn glow_tab<'a>(label: &'a str, tab: Tab, current: Tab, count: usize) -> Element
     .into()
 }
 
-fn result_row<'a>(result: &'a CheckResult) -> Element<'a, Message> {
+fn result_row<'a>(
+    result: &'a CheckResult,
+    score: Option<u8>,
+    last_known: Option<&LastKnown>,
+) -> Element<'a, Message> {
     let (icon, color) = match &result.status {
         ResultStatus::Available => ("●", theme::SUCCESS),
         ResultStatus::Taken => ("○", theme::DANGER),
-        ResultStatus::Error(_) | ResultStatus::Invalid => ("◌", theme::WARNING),
+        ResultStatus::Error(_) | ResultStatus::Unknown | ResultStatus::Invalid => {
+            ("◌", theme::WARNING)
+        }
     };
 
     container(
@@ -716,12 +2951,34 @@ fn result_row<'a>(result: &'a CheckResult) -> Element<'a, Message> {
             text(&result.username)
                 .size(12)
                 .color(theme::TEXT_PRIMARY),
+            last_known.map(|last| {
+                row![
+                    space::horizontal().width(10),
+                    text(format!(
+                        "was {} on {}",
+                        last.status.label(),
+                        last.checked_at.with_timezone(&Local).format("%Y-%m-%d")
+                    ))
+                    .size(10)
+                    .color(theme::TEXT_MUTED),
+                ]
+            }),
             space::horizontal().width(Fill),
+            score.map(|score| {
+                row![
+                    text(score.to_string()).size(11).color(if score >= 70 {
+                        theme::SUCCESS
+                    } else if score >= 40 {
+                        theme::TEXT_SECONDARY
+                    } else {
+                        theme::TEXT_MUTED
+                    }),
+                    space::horizontal().width(12),
+                ]
+            }),
             text(match &result.status {
-                ResultStatus::Available => "available",
-                ResultStatus::Taken => "taken",
-                ResultStatus::Error(e) => e.as_str(),
-                ResultStatus::Invalid => "invalid",
+                ResultStatus::Error(e) => e.to_string(),
+                status => status.label().to_string(),
             })
             .size(10)
             .color(color),
@@ -740,26 +2997,3 @@ fn result_row<'a>(result: &'a CheckResult) -> Element<'a, Message> {
     })
     .into()
 }
-
-async fn export_to_file(usernames: Vec<String>) -> Result<String, String> {
-    let dialog = rfd::AsyncFileDialog::new()
-        .add_filter("Text files", &["txt"])
-        .set_file_name(format!(
-            "hytale_available_{}.txt",
-            Local::now().format("%Y%m%d_%H%M%S")
-        ))
-        .save_file()
-        .await;
-
-    match dialog {
-        Some(handle) => {
-            let content = usernames.join("\n");
-            handle
-                .write(content.as_bytes())
-                .await
-                .map(|_| handle.path().to_string_lossy().to_string())
-                .map_err(|e| e.to_string())
-        }
-        None => Err("Cancelled".into()),
-    }
-}
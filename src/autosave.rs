@@ -0,0 +1,78 @@
+use hytale_checker_core::{CheckResult, NdjsonWriter, ResultStatus};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutoSaveTarget {
+    Hits,
+    Errors,
+    /// Every result as one JSON object per line, for other tools to follow.
+    Ndjson,
+}
+
+/// Appends usernames, or NDJSON records, to the chosen files as results
+/// arrive, flushing after each line so nothing is lost if the app goes away
+/// mid-run.
+pub struct AutoSave {
+    hits: Option<File>,
+    errors: Option<File>,
+    ndjson: Option<NdjsonWriter<File>>,
+}
+
+impl AutoSave {
+    pub fn open(
+        hits: Option<&Path>,
+        errors: Option<&Path>,
+        ndjson: Option<&Path>,
+    ) -> Result<Self, String> {
+        Ok(Self {
+            hits: hits.map(open_append).transpose()?,
+            errors: errors.map(open_append).transpose()?,
+            ndjson: ndjson.map(open_append).transpose()?.map(NdjsonWriter::new),
+        })
+    }
+
+    pub fn record(&mut self, result: &CheckResult) -> io::Result<()> {
+        if let Some(ndjson) = &mut self.ndjson {
+            ndjson.write(result)?;
+        }
+
+        let file = match result.status {
+            ResultStatus::Available => self.hits.as_mut(),
+            _ if result.status.is_retryable() => self.errors.as_mut(),
+            _ => None,
+        };
+
+        match file {
+            Some(file) => {
+                writeln!(file, "{}", result.username)?;
+                file.flush()
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+fn open_append(path: &Path) -> Result<File, String> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+pub async fn choose_file(target: AutoSaveTarget) -> Option<PathBuf> {
+    let (name, filter, extensions): (_, _, &[&str]) = match target {
+        AutoSaveTarget::Hits => ("hytale_available.txt", "Text files", &["txt"]),
+        AutoSaveTarget::Errors => ("hytale_errors.txt", "Text files", &["txt"]),
+        AutoSaveTarget::Ndjson => ("hytale_results.ndjson", "NDJSON", &["ndjson", "jsonl"]),
+    };
+
+    rfd::AsyncFileDialog::new()
+        .add_filter(filter, extensions)
+        .set_file_name(name)
+        .save_file()
+        .await
+        .map(|handle| handle.path().to_path_buf())
+}
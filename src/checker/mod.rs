@@ -1,5 +0,0 @@
-pub mod api;
-pub mod types;
-
-pub use api::*;
-pub use types::*;
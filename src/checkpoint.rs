@@ -0,0 +1,107 @@
+use hytale_checker_core::{CheckResult, Pattern, ProxyType, ResultStatus, UsernameRules};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::config;
+
+const CHECKPOINT_FILE: &str = "checkpoint.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub saved_at: String,
+    pub usernames: Vec<String>,
+    pub settings: RunSettings,
+    pub results: Vec<CheckResult>,
+    /// Set when the usernames came from the generator; they're expanded again
+    /// on resume instead of being stored.
+    #[serde(default)]
+    pub pattern: Option<String>,
+    /// Set when the usernames came from a file too long to store here; it's
+    /// read again on resume, ahead of `usernames`.
+    #[serde(default)]
+    pub file: Option<PathBuf>,
+    /// Names left out of the run as recently taken.
+    #[serde(default)]
+    pub skipped: Vec<String>,
+    /// How many names a list run covers once deduplicated and validated.
+    /// Older checkpoints stored only the names that were checked.
+    #[serde(default)]
+    pub total: Option<usize>,
+}
+
+/// The settings a run was started with, as entered in the UI.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RunSettings {
+    pub proxy_type: ProxyType,
+    pub proxies: Vec<String>,
+    pub rate_limit: String,
+    pub proxy_rate_limit: String,
+    pub concurrency: String,
+    pub adaptive_concurrency: bool,
+    pub max_attempts: String,
+    pub skip_taken_days: String,
+}
+
+impl Default for RunSettings {
+    fn default() -> Self {
+        Self {
+            proxy_type: ProxyType::None,
+            proxies: Vec::new(),
+            rate_limit: "10".to_string(),
+            proxy_rate_limit: String::new(),
+            concurrency: "5".to_string(),
+            adaptive_concurrency: false,
+            max_attempts: "5".to_string(),
+            skip_taken_days: String::new(),
+        }
+    }
+}
+
+impl Checkpoint {
+    /// Results that settled the username for good, including names the rules
+    /// reject; everything else is re-checked on resume.
+    pub fn definitive_results(&self) -> impl Iterator<Item = &CheckResult> {
+        self.results
+            .iter()
+            .filter(|r| r.status.is_definitive() || r.status == ResultStatus::Invalid)
+    }
+
+    pub fn definitive_count(&self) -> usize {
+        self.definitive_results().count()
+    }
+
+    /// How many usernames the run covers.
+    pub fn total(&self, rules: &UsernameRules) -> usize {
+        match &self.pattern {
+            Some(pattern) => Pattern::parse(pattern)
+                .map(|p| usize::try_from(p.valid_count(rules)).unwrap_or(usize::MAX))
+                .unwrap_or(0),
+            None => self.total.unwrap_or(self.usernames.len()),
+        }
+    }
+}
+
+fn checkpoint_path() -> Option<PathBuf> {
+    config::data_dir().map(|dir| dir.join(CHECKPOINT_FILE))
+}
+
+pub async fn load() -> Option<Checkpoint> {
+    let path = checkpoint_path()?;
+    let data = tokio::fs::read(path).await.ok()?;
+    serde_json::from_slice(&data).ok()
+}
+
+pub async fn save(checkpoint: Checkpoint) -> Result<(), String> {
+    let path = checkpoint_path().ok_or("No data directory available")?;
+    let data = serde_json::to_vec(&checkpoint).map_err(|e| e.to_string())?;
+    tokio::task::spawn_blocking(move || config::write_atomic(&path, &data))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+pub async fn remove() {
+    if let Some(path) = checkpoint_path() {
+        let _ = tokio::fs::remove_file(path).await;
+    }
+}
@@ -0,0 +1,427 @@
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use futures::StreamExt;
+use hytale_checker_core::{
+    check_usernames_stream, mutate, ndjson, CheckEvent, CheckResult, Concurrency, JsonBackend,
+    Mutation, NdjsonWriter, Notifier, Pattern, ProxyType, RateLimit, ResultStatus, RetryPolicy,
+    Stats, WebhookEvent,
+};
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+
+use crate::config;
+use crate::history::{self, History, Recorder, Source};
+
+#[derive(Parser)]
+#[command(version, about = "Check Hytale username availability")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Check usernames without opening the window
+    Check(CheckArgs),
+    /// Show the last known status of usernames from earlier runs
+    History(HistoryArgs),
+    /// Print the valid usernames a pattern expands to
+    Generate(GenerateArgs),
+    /// Print the valid usernames derived from a wordlist
+    Mutate(MutateArgs),
+}
+
+#[derive(Args)]
+pub struct CheckArgs {
+    /// File with one username per line; reads stdin when omitted or "-"
+    input: Option<PathBuf>,
+
+    /// File with one proxy per line (host:port or user:pass@host:port)
+    #[arg(long)]
+    proxies: Option<PathBuf>,
+
+    /// Protocol for proxies given without a scheme
+    #[arg(long, default_value = "http")]
+    proxy_type: ProxyType,
+
+    /// Global request rate in requests per second (0 = unlimited)
+    #[arg(long, default_value_t = 10.0)]
+    rate: f64,
+
+    /// Per-proxy request rate in requests per second (0 = unlimited)
+    #[arg(long, default_value_t = 0.0)]
+    proxy_rate: f64,
+
+    /// Concurrent requests (the starting point with --auto)
+    #[arg(long, default_value_t = 5)]
+    threads: usize,
+
+    /// Adapt concurrency to observed rate limits and latency
+    #[arg(long)]
+    auto: bool,
+
+    /// Attempts per username when rate limited
+    #[arg(long, default_value_t = 5)]
+    attempts: u32,
+
+    /// Output format for stdout
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+
+    /// Also append every result as NDJSON to this file
+    #[arg(long, value_name = "FILE")]
+    ndjson: Option<PathBuf>,
+
+    /// Skip usernames found Taken within this many days
+    #[arg(long, value_name = "DAYS")]
+    skip_taken_days: Option<u32>,
+
+    /// Don't record results in the history database
+    #[arg(long)]
+    no_history: bool,
+}
+
+#[derive(Args)]
+pub struct HistoryArgs {
+    /// Usernames to look up
+    #[arg(required = true)]
+    usernames: Vec<String>,
+}
+
+#[derive(Args)]
+pub struct GenerateArgs {
+    /// Pattern such as "[c][v][c]<0-9>" or "(the|)[a-z]{3}"; c and v inside [...] (or \c and
+    /// \v anywhere) are a consonant and a vowel
+    pattern: String,
+
+    /// Only print how many names the pattern expands to
+    #[arg(long)]
+    count: bool,
+
+    /// Stop after this many names
+    #[arg(long, value_name = "N")]
+    limit: Option<usize>,
+}
+
+#[derive(Args)]
+pub struct MutateArgs {
+    /// File with one seed word per line; reads stdin when omitted or "-"
+    wordlist: Option<PathBuf>,
+
+    /// Mutations to apply, comma-separated: leetspeak, underscores, digits,
+    /// doubling, truncation, plural, combine
+    #[arg(long = "with", value_name = "MUTATIONS", value_delimiter = ',')]
+    mutations: Vec<Mutation>,
+
+    /// Only print how many names each mutation adds
+    #[arg(long)]
+    count: bool,
+
+    /// Stop after this many names
+    #[arg(long, value_name = "N")]
+    limit: Option<usize>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// Tab-separated `username  status  [error]`
+    Text,
+    /// One JSON object per line
+    Ndjson,
+}
+
+/// Runs a headless check. Results go to stdout, progress to stderr; exits
+/// with 1 if any check failed with an error and 2 if the run couldn't start.
+/// Invalid names and unknown answers don't fail the run.
+pub fn run(command: Command) -> ExitCode {
+    let result = match command {
+        Command::Check(args) => run_check(args),
+        Command::History(args) => run_history(args).map(|()| 0),
+        Command::Generate(args) => run_generate(args).map(|()| 0),
+        Command::Mutate(args) => run_mutate(args).map(|()| 0),
+    };
+
+    match result {
+        Ok(failed) if failed > 0 => ExitCode::from(1),
+        Ok(_) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Returns how many checks failed with an error.
+fn run_check(args: CheckArgs) -> Result<usize, String> {
+    let usernames = match &args.input {
+        Some(path) if path.as_os_str() != "-" => read_lines_from_file(path)?,
+        _ => read_lines(io::stdin().lock()).map_err(|e| format!("stdin: {}", e))?,
+    };
+    if usernames.is_empty() {
+        return Err("no usernames to check".into());
+    }
+
+    let history = if args.no_history {
+        None
+    } else {
+        History::open()
+            .inspect_err(|e| eprintln!("warning: history unavailable: {}", e))
+            .ok()
+    };
+
+    let usernames = match (&history, args.skip_taken_days) {
+        (Some(history), Some(days)) if days > 0 => {
+            let total = usernames.len();
+            let known = history.last_known_all(usernames.iter().cloned(), chrono::Utc::now())?;
+            let window = chrono::Duration::days(days.into());
+            let usernames: Vec<String> = usernames
+                .into_iter()
+                .filter(|u| !history::is_fresh_taken(known.get(u), window))
+                .collect();
+            eprintln!(
+                "Skipped {} usernames taken within the last {} days",
+                total - usernames.len(),
+                days
+            );
+            usernames
+        }
+        _ => usernames,
+    };
+    if usernames.is_empty() {
+        return Ok(0);
+    }
+    let history_run = match &history {
+        Some(history) => Some(history.start_run(Source::Cli)?),
+        None => None,
+    };
+    let recorder = history.map(Recorder::start).transpose()?;
+
+    let proxies = match &args.proxies {
+        Some(path) if args.proxy_type != ProxyType::None => read_lines_from_file(path)?
+            .iter()
+            .map(|p| args.proxy_type.format_proxy(p))
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let mut ndjson_file = match &args.ndjson {
+        Some(path) => Some(NdjsonWriter::new(
+            std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|e| format!("{}: {}", path.display(), e))?,
+        )),
+        None => None,
+    };
+
+    let backend =
+        Arc::new(JsonBackend::new(config::load_backend()?).with_rules(config::load_rules()?));
+    let notifier = Arc::new(Notifier::new(config::load_webhooks()?)?);
+    let rate = RateLimit {
+        global_rps: args.rate,
+        per_proxy_rps: args.proxy_rate,
+    };
+    let threads = args.threads.max(1);
+    let concurrency = if args.auto {
+        Concurrency::adaptive(threads)
+    } else {
+        Concurrency::Fixed(threads)
+    };
+    let retry = RetryPolicy {
+        max_attempts: args.attempts.max(1),
+        ..Default::default()
+    };
+
+    let runtime = tokio::runtime::Runtime::new().map_err(|e| e.to_string())?;
+    let (stats, failed) = runtime.block_on(async move {
+        let mut stats = Stats {
+            total: usernames.len(),
+            ..Default::default()
+        };
+        let mut failed = 0;
+        let mut recorder = recorder;
+        let mut deliveries = Vec::new();
+        let (mut events, _handle) =
+            check_usernames_stream(usernames, proxies, rate, concurrency, retry, backend);
+
+        let mut stdout = io::stdout().lock();
+        let show_progress = io::stderr().is_terminal();
+
+        while let Some(event) = events.next().await {
+            match event {
+                CheckEvent::Result(result) => {
+                    stats.add(&result.status);
+                    stats.checked += 1;
+                    if matches!(result.status, ResultStatus::Error(_)) {
+                        failed += 1;
+                    }
+                    let line = match args.format {
+                        OutputFormat::Text => format_result(&result),
+                        OutputFormat::Ndjson => ndjson::to_line(&result),
+                    };
+                    let _ = writeln!(stdout, "{}", line);
+                    if let Some(Err(e)) = ndjson_file.as_mut().map(|f| f.write(&result)) {
+                        eprintln!("\nerror: writing NDJSON: {}", e);
+                        ndjson_file = None;
+                    }
+                    if let (Some(r), Some(run_id)) = (&recorder, history_run)
+                        && let Err(e) = r.record(run_id, &result)
+                    {
+                        eprintln!("\nerror: writing history: {}", e);
+                        recorder = None;
+                    }
+                    if result.status == ResultStatus::Available && !notifier.is_empty() {
+                        let notifier = notifier.clone();
+                        let event = WebhookEvent::Available {
+                            username: result.username.clone(),
+                            checked_at: result.checked_at,
+                        };
+                        deliveries.push(tokio::spawn(async move { notifier.notify(event).await }));
+                    }
+                    if show_progress {
+                        eprint!("\r{}", progress_line(&stats));
+                    }
+                }
+                CheckEvent::Concurrency(_) => {}
+                CheckEvent::Done => break,
+            }
+        }
+        let _ = stdout.flush();
+        if let Some(Err(e)) = recorder.map(Recorder::finish) {
+            eprintln!("\nerror: writing history: {}", e);
+        }
+
+        if !notifier.is_empty() {
+            let event = WebhookEvent::RunComplete {
+                stats: stats.clone(),
+            };
+            deliveries.push(tokio::spawn(async move { notifier.notify(event).await }));
+        }
+        let mut failures = Vec::new();
+        for delivery in deliveries {
+            failures.extend(delivery.await.unwrap_or_default());
+        }
+        if !failures.is_empty() {
+            if io::stderr().is_terminal() {
+                eprintln!();
+            }
+            for failure in &failures {
+                eprintln!("error: webhook {}", failure);
+            }
+            config::log_webhook_failures(&failures);
+        }
+        (stats, failed)
+    });
+
+    if io::stderr().is_terminal() {
+        eprintln!();
+    }
+    eprintln!("{}", progress_line(&stats));
+    Ok(failed)
+}
+
+fn run_history(args: HistoryArgs) -> Result<(), String> {
+    let history = History::open()?;
+    for username in &args.usernames {
+        match history.last_known(username)? {
+            Some(last) => println!(
+                "{}\t{}\t{}",
+                username,
+                last.status.label(),
+                last.checked_at
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M")
+            ),
+            None => println!("{}\tnever checked", username),
+        }
+    }
+    Ok(())
+}
+
+fn run_generate(args: GenerateArgs) -> Result<(), String> {
+    let pattern = Pattern::parse(&args.pattern).map_err(|e| e.to_string())?;
+    let rules = config::load_rules()?;
+    if args.count {
+        let bound = if pattern.is_count_exact(&rules) {
+            ""
+        } else {
+            "≤ "
+        };
+        println!(
+            "{} candidates, {}{} valid",
+            pattern.count(),
+            bound,
+            pattern.valid_count(&rules)
+        );
+        return Ok(());
+    }
+
+    let limit = args.limit.unwrap_or(usize::MAX);
+    let mut out = io::BufWriter::new(io::stdout().lock());
+    for name in pattern.valid_names(rules).take(limit) {
+        if writeln!(out, "{}", name).is_err() {
+            // The reader went away, e.g. piped into `head`.
+            return Ok(());
+        }
+    }
+    out.flush().or(Ok(()))
+}
+
+fn run_mutate(args: MutateArgs) -> Result<(), String> {
+    let seeds = match &args.wordlist {
+        Some(path) if path.as_os_str() != "-" => read_lines_from_file(path)?,
+        _ => read_lines(io::stdin().lock()).map_err(|e| format!("stdin: {}", e))?,
+    };
+    let rules = config::load_rules()?;
+    let limit = args.limit.unwrap_or(usize::MAX);
+    let mutated = mutate(&seeds, &args.mutations, &rules, limit);
+
+    if args.count {
+        println!("{}\tseed words", mutated.seeds);
+        for (mutation, added) in &mutated.added {
+            println!("+{}\t{}", added, mutation.to_string().to_ascii_lowercase());
+        }
+        println!("{}\ttotal", mutated.names.len());
+        if mutated.truncated {
+            eprintln!("Stopped at the limit of {} names", limit);
+        }
+        return Ok(());
+    }
+
+    let mut out = io::BufWriter::new(io::stdout().lock());
+    for name in &mutated.names {
+        if writeln!(out, "{}", name).is_err() {
+            return Ok(());
+        }
+    }
+    out.flush().or(Ok(()))
+}
+
+fn format_result(result: &CheckResult) -> String {
+    match &result.status {
+        ResultStatus::Error(e) => format!("{}\t{}\t{}", result.username, result.status.label(), e),
+        status => format!("{}\t{}", result.username, status.label()),
+    }
+}
+
+fn progress_line(stats: &Stats) -> String {
+    format!(
+        "{}/{} checked · {} available · {} taken · {} errors",
+        stats.checked, stats.total, stats.available, stats.taken, stats.errors
+    )
+}
+
+fn read_lines_from_file(path: &PathBuf) -> Result<Vec<String>, String> {
+    let file = std::fs::File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    read_lines(io::BufReader::new(file)).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn read_lines(reader: impl BufRead) -> io::Result<Vec<String>> {
+    reader
+        .lines()
+        .map(|line| line.map(|l| l.trim().to_string()))
+        .filter(|line| !matches!(line, Ok(l) if l.is_empty()))
+        .collect()
+}
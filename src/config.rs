@@ -0,0 +1,127 @@
+use chrono::Local;
+use hytale_checker_core::{BackendConfig, UsernameRules, WebhookConfig};
+use serde::de::DeserializeOwned;
+use std::fmt::Display;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const APP_DIR: &str = "hytale-checker";
+const BACKEND_FILE: &str = "backend.json";
+const RULES_FILE: &str = "rules.json";
+const WEBHOOKS_FILE: &str = "webhooks.json";
+const WEBHOOK_LOG: &str = "webhooks.log";
+
+pub fn config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(APP_DIR))
+}
+
+pub fn data_dir() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join(APP_DIR))
+}
+
+/// Reads the API endpoint override, if one exists; otherwise hytl.tools is used.
+pub fn load_backend() -> Result<BackendConfig, String> {
+    load_json(config_dir().map(|dir| dir.join(BACKEND_FILE)))
+}
+
+/// Reads the username rules override, if one exists; otherwise Hytale's
+/// defaults apply.
+pub fn load_rules() -> Result<UsernameRules, String> {
+    load_json(config_dir().map(|dir| dir.join(RULES_FILE)))
+}
+
+/// Reads the outgoing webhooks, if any are configured.
+pub fn load_webhooks() -> Result<Vec<WebhookConfig>, String> {
+    load_json(config_dir().map(|dir| dir.join(WEBHOOKS_FILE)))
+}
+
+/// Reads a JSON file, falling back to the default when it doesn't exist.
+pub fn load_json<T: DeserializeOwned + Default>(path: Option<PathBuf>) -> Result<T, String> {
+    load_or_default(path, |data| serde_json::from_slice(data))
+}
+
+/// Reads and parses a file, falling back to the default when there's no
+/// directory to look in or the file doesn't exist yet.
+pub fn load_or_default<T: Default, E: Display>(
+    path: Option<PathBuf>,
+    parse: impl FnOnce(&[u8]) -> Result<T, E>,
+) -> Result<T, String> {
+    let Some(path) = path else {
+        return Ok(T::default());
+    };
+
+    match std::fs::read(&path) {
+        Ok(data) => parse(&data).map_err(|e| format!("Invalid {}: {}", path.display(), e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(T::default()),
+        Err(e) => Err(format!("Couldn't read {}: {}", path.display(), e)),
+    }
+}
+
+/// Replaces `path` with `data` by writing a sibling temp file and renaming it
+/// over, so a crash mid-write never leaves a truncated file behind.
+pub fn write_atomic(path: &Path, data: &[u8]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    // Synced before the rename, so a crash can't leave an empty or partial
+    // file in place of the old one.
+    let mut file = std::fs::File::create(&tmp).map_err(|e| e.to_string())?;
+    file.write_all(data)
+        .and_then(|()| file.sync_all())
+        .map_err(|e| e.to_string())?;
+    drop(file);
+    std::fs::rename(&tmp, path).map_err(|e| e.to_string())
+}
+
+/// Appends failed webhook deliveries to the log in the data directory.
+pub fn log_webhook_failures(failures: &[String]) {
+    if let Some(dir) = data_dir() {
+        let _ = append_log(&dir.join(WEBHOOK_LOG), failures);
+    }
+}
+
+/// Appends each line to `path` with a timestamp, creating the file and its
+/// directory as needed.
+fn append_log(path: &Path, lines: &[String]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    let now = Local::now().format("%Y-%m-%d %H:%M:%S");
+    for line in lines {
+        writeln!(file, "{} {}", now, line)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn appends_webhook_failures_with_timestamps() {
+        let dir = std::env::temp_dir().join(format!("hytale-checker-log-{}", std::process::id()));
+        let path = dir.join(WEBHOOK_LOG);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        append_log(&path, &["first".to_string()]).unwrap();
+        append_log(&path, &["second".to_string(), "third".to_string()]).unwrap();
+
+        let log = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = log.lines().collect();
+        assert_eq!(lines.len(), 3);
+        for (line, failure) in lines.iter().zip(["first", "second", "third"]) {
+            let (timestamp, rest) = line.split_at(19);
+            assert!(chrono::NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S").is_ok());
+            assert_eq!(rest, format!(" {}", failure));
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
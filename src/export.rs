@@ -0,0 +1,403 @@
+use chrono::{DateTime, Local, SecondsFormat};
+use hytale_checker_core::ndjson::ResultRecord;
+use hytale_checker_core::{score, CheckResult, ProxyType, ResultStatus, Stats};
+use serde::Serialize;
+
+use crate::checkpoint::RunSettings;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResultCategory {
+    Available,
+    Taken,
+    Errors,
+    Invalid,
+}
+
+impl ResultCategory {
+    pub const ALL: [ResultCategory; 4] = [
+        ResultCategory::Available,
+        ResultCategory::Taken,
+        ResultCategory::Errors,
+        ResultCategory::Invalid,
+    ];
+
+    pub fn matches(&self, status: &ResultStatus) -> bool {
+        match self {
+            ResultCategory::Available => *status == ResultStatus::Available,
+            ResultCategory::Taken => *status == ResultStatus::Taken,
+            ResultCategory::Errors => status.is_retryable(),
+            ResultCategory::Invalid => *status == ResultStatus::Invalid,
+        }
+    }
+}
+
+impl std::fmt::Display for ResultCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResultCategory::Available => write!(f, "Available"),
+            ResultCategory::Taken => write!(f, "Taken"),
+            ResultCategory::Errors => write!(f, "Errors"),
+            ResultCategory::Invalid => write!(f, "Invalid"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    #[default]
+    Text,
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 3] = [ExportFormat::Text, ExportFormat::Csv, ExportFormat::Json];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Text => "txt",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+impl std::fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportFormat::Text => write!(f, "Plain text"),
+            ExportFormat::Csv => write!(f, "CSV"),
+            ExportFormat::Json => write!(f, "JSON"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    pub categories: Vec<ResultCategory>,
+    pub format: ExportFormat,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            categories: vec![ResultCategory::Available],
+            format: ExportFormat::Text,
+        }
+    }
+}
+
+impl ExportOptions {
+    pub fn toggle(&mut self, category: ResultCategory) {
+        if let Some(pos) = self.categories.iter().position(|c| *c == category) {
+            self.categories.remove(pos);
+        } else {
+            self.categories.push(category);
+        }
+    }
+
+    pub fn includes(&self, status: &ResultStatus) -> bool {
+        self.categories.iter().any(|c| c.matches(status))
+    }
+}
+
+/// Context for a JSON export. Proxy addresses are left out since they may
+/// carry credentials.
+pub struct RunMetadata {
+    pub settings: RunSettings,
+    pub started_at: Option<DateTime<Local>>,
+    pub finished_at: Option<DateTime<Local>>,
+    pub stats: Stats,
+}
+
+#[derive(Serialize)]
+struct JsonExport<'a> {
+    exported_at: String,
+    started_at: Option<String>,
+    finished_at: Option<String>,
+    settings: JsonSettings<'a>,
+    stats: &'a Stats,
+    results: Vec<JsonResult<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonResult<'a> {
+    #[serde(flatten)]
+    record: ResultRecord<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    score: Option<u8>,
+}
+
+#[derive(Serialize)]
+struct JsonSettings<'a> {
+    proxy_type: ProxyType,
+    proxy_count: usize,
+    rate_limit: &'a str,
+    proxy_rate_limit: &'a str,
+    concurrency: &'a str,
+    adaptive_concurrency: bool,
+    max_attempts: &'a str,
+}
+
+pub fn render(
+    results: &[&CheckResult],
+    format: ExportFormat,
+    metadata: &RunMetadata,
+) -> Result<String, String> {
+    match format {
+        ExportFormat::Text => Ok(results
+            .iter()
+            .map(|r| r.username.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")),
+        ExportFormat::Csv => Ok(render_csv(results)),
+        ExportFormat::Json => render_json(results, metadata),
+    }
+}
+
+fn render_csv(results: &[&CheckResult]) -> String {
+    let mut out = String::from(
+        "username,status,error_kind,http_status,error,proxy,attempts,latency_ms,checked_at,score\n",
+    );
+    for result in results {
+        let record = ResultRecord::from(*result);
+        let (kind, http_status, error) = match &record.error {
+            Some(e) => (
+                serde_json::to_value(e.kind)
+                    .ok()
+                    .and_then(|v| v.as_str().map(str::to_string))
+                    .unwrap_or_default(),
+                e.http_status.map(|s| s.to_string()).unwrap_or_default(),
+                e.detail.to_string(),
+            ),
+            None => Default::default(),
+        };
+        let fields = [
+            record.username.to_string(),
+            record.status.to_string(),
+            kind,
+            http_status,
+            error,
+            record.proxy.unwrap_or_default().to_string(),
+            record.attempts.to_string(),
+            record.latency_ms.to_string(),
+            record.timestamp,
+            available_score(result)
+                .map(|s| s.to_string())
+                .unwrap_or_default(),
+        ];
+        let line: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        out.push_str(&line.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Scores are only meaningful for names that can actually be claimed.
+fn available_score(result: &CheckResult) -> Option<u8> {
+    (result.status == ResultStatus::Available).then(|| score(&result.username).total)
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn render_json(results: &[&CheckResult], metadata: &RunMetadata) -> Result<String, String> {
+    let timestamp = |t: &DateTime<Local>| t.to_rfc3339_opts(SecondsFormat::Secs, false);
+    let settings = &metadata.settings;
+
+    let export = JsonExport {
+        exported_at: timestamp(&Local::now()),
+        started_at: metadata.started_at.as_ref().map(timestamp),
+        finished_at: metadata.finished_at.as_ref().map(timestamp),
+        settings: JsonSettings {
+            proxy_type: settings.proxy_type,
+            proxy_count: settings.proxies.len(),
+            rate_limit: &settings.rate_limit,
+            proxy_rate_limit: &settings.proxy_rate_limit,
+            concurrency: &settings.concurrency,
+            adaptive_concurrency: settings.adaptive_concurrency,
+            max_attempts: &settings.max_attempts,
+        },
+        stats: &metadata.stats,
+        results: results
+            .iter()
+            .map(|r| JsonResult {
+                record: ResultRecord::from(*r),
+                score: available_score(r),
+            })
+            .collect(),
+    };
+
+    serde_json::to_string_pretty(&export).map_err(|e| e.to_string())
+}
+
+pub async fn save_to_file(
+    content: String,
+    format: ExportFormat,
+    name: &'static str,
+) -> Result<String, String> {
+    let extension = format.extension();
+    let dialog = rfd::AsyncFileDialog::new()
+        .add_filter(format.to_string(), &[extension])
+        .set_file_name(format!(
+            "hytale_{}_{}.{}",
+            name,
+            Local::now().format("%Y%m%d_%H%M%S"),
+            extension
+        ))
+        .save_file()
+        .await;
+
+    match dialog {
+        Some(handle) => handle
+            .write(content.as_bytes())
+            .await
+            .map(|_| handle.path().to_string_lossy().to_string())
+            .map_err(|e| e.to_string()),
+        None => Err("Cancelled".into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use hytale_checker_core::{CheckError, ErrorKind};
+    use serde_json::{json, Value};
+    use std::time::Duration;
+
+    fn result(username: &str, status: ResultStatus) -> CheckResult {
+        CheckResult {
+            username: username.to_string(),
+            status,
+            attempts: 3,
+            latency: Duration::from_millis(120),
+            proxy: Some("1.2.3.4:8080".to_string()),
+            checked_at: Utc.with_ymd_and_hms(2026, 1, 2, 3, 4, 5).unwrap(),
+        }
+    }
+
+    fn metadata() -> RunMetadata {
+        RunMetadata {
+            settings: RunSettings {
+                proxy_type: ProxyType::Http,
+                proxies: vec![
+                    "user:secret@1.2.3.4:8080".to_string(),
+                    "5.6.7.8:3128".to_string(),
+                ],
+                ..RunSettings::default()
+            },
+            started_at: Some(Local.with_ymd_and_hms(2026, 1, 2, 3, 0, 0).unwrap()),
+            finished_at: None,
+            stats: Stats {
+                total: 2,
+                checked: 2,
+                available: 1,
+                taken: 0,
+                errors: 1,
+            },
+        }
+    }
+
+    #[test]
+    fn csv_quotes_commas_quotes_and_newlines() {
+        let error =
+            CheckError::new(ErrorKind::ServerError, "bad \"gateway\", try\nagain").with_status(502);
+        let results = [
+            result("freebie", ResultStatus::Available),
+            result("broken", ResultStatus::Error(error)),
+        ];
+        let csv = render(&[&results[0], &results[1]], ExportFormat::Csv, &metadata()).unwrap();
+
+        let expected = format!(
+            "username,status,error_kind,http_status,error,proxy,attempts,latency_ms,checked_at,score\n\
+             freebie,available,,,,1.2.3.4:8080,3,120,2026-01-02T03:04:05.000Z,{}\n\
+             broken,error,server_error,502,\"bad \"\"gateway\"\", try\nagain\",1.2.3.4:8080,3,120,2026-01-02T03:04:05.000Z,\n",
+            score("freebie").total
+        );
+        assert_eq!(csv, expected);
+        assert_eq!(csv_field("carriage\rreturn"), "\"carriage\rreturn\"");
+        assert_eq!(csv_field("plain"), "plain");
+    }
+
+    #[test]
+    fn json_wraps_results_in_run_metadata() {
+        let results = [
+            result("freebie", ResultStatus::Available),
+            result("someone", ResultStatus::Taken),
+        ];
+        let metadata = metadata();
+        let rendered = render(&[&results[0], &results[1]], ExportFormat::Json, &metadata).unwrap();
+        assert!(!rendered.contains("secret"));
+        let export: Value = serde_json::from_str(&rendered).unwrap();
+
+        let mut keys: Vec<&str> = export
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        keys.sort_unstable();
+        assert_eq!(
+            keys,
+            [
+                "exported_at",
+                "finished_at",
+                "results",
+                "settings",
+                "started_at",
+                "stats"
+            ]
+        );
+        let started = export["started_at"].as_str().unwrap();
+        assert_eq!(
+            DateTime::parse_from_rfc3339(started).unwrap(),
+            metadata.started_at.unwrap()
+        );
+        assert!(DateTime::parse_from_rfc3339(export["exported_at"].as_str().unwrap()).is_ok());
+        assert_eq!(export["finished_at"], Value::Null);
+        assert_eq!(
+            export["settings"],
+            json!({
+                "proxy_type": "Http",
+                "proxy_count": 2,
+                "rate_limit": "10",
+                "proxy_rate_limit": "",
+                "concurrency": "5",
+                "adaptive_concurrency": false,
+                "max_attempts": "5",
+            })
+        );
+        assert_eq!(
+            export["stats"],
+            json!({ "total": 2, "checked": 2, "available": 1, "taken": 0, "errors": 1 })
+        );
+        assert_eq!(
+            export["results"],
+            json!([
+                {
+                    "username": "freebie",
+                    "status": "available",
+                    "proxy": "1.2.3.4:8080",
+                    "latency_ms": 120,
+                    "attempts": 3,
+                    "timestamp": "2026-01-02T03:04:05.000Z",
+                    "score": score("freebie").total,
+                },
+                {
+                    "username": "someone",
+                    "status": "taken",
+                    "proxy": "1.2.3.4:8080",
+                    "latency_ms": 120,
+                    "attempts": 3,
+                    "timestamp": "2026-01-02T03:04:05.000Z",
+                },
+            ])
+        );
+    }
+}
@@ -0,0 +1,309 @@
+use chrono::{DateTime, Duration, Utc};
+use hytale_checker_core::{CheckResult, ResultStatus};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread::JoinHandle;
+
+use crate::config;
+
+const HISTORY_FILE: &str = "history.sqlite3";
+/// Most results [`Recorder`] writes in one transaction, after the first.
+const RECORD_BATCH_LIMIT: usize = 1_000;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS runs (
+        id INTEGER PRIMARY KEY,
+        source TEXT NOT NULL,
+        started_at INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS checks (
+        id INTEGER PRIMARY KEY,
+        username TEXT NOT NULL COLLATE NOCASE,
+        status TEXT NOT NULL,
+        checked_at INTEGER NOT NULL,
+        run_id INTEGER NOT NULL REFERENCES runs(id)
+    );
+    CREATE INDEX IF NOT EXISTS checks_username ON checks(username, checked_at);
+";
+
+/// Where a run was started from.
+#[derive(Debug, Clone, Copy)]
+pub enum Source {
+    Gui,
+    Cli,
+    Watch,
+}
+
+impl Source {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Source::Gui => "gui",
+            Source::Cli => "cli",
+            Source::Watch => "watch",
+        }
+    }
+}
+
+/// The most recent definitive answer for a username.
+#[derive(Debug, Clone)]
+pub struct LastKnown {
+    pub status: ResultStatus,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// Every check ever performed, kept in a SQLite database in the data directory.
+pub struct History {
+    conn: Connection,
+}
+
+impl History {
+    pub fn open() -> Result<Self, String> {
+        let dir = config::data_dir().ok_or("No data directory available")?;
+        std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+        Self::open_at(&dir.join(HISTORY_FILE))
+    }
+
+    fn open_at(path: &Path) -> Result<Self, String> {
+        let conn = Connection::open(path).map_err(|e| e.to_string())?;
+        conn.pragma_update(None, "journal_mode", "WAL")
+            .and_then(|_| conn.pragma_update(None, "synchronous", "NORMAL"))
+            .and_then(|_| conn.execute_batch(SCHEMA))
+            .map_err(|e| format!("History database: {}", e))?;
+        Ok(Self { conn })
+    }
+
+    pub fn start_run(&self, source: Source) -> Result<i64, String> {
+        self.conn
+            .execute(
+                "INSERT INTO runs (source, started_at) VALUES (?1, ?2)",
+                params![source.as_str(), Utc::now().timestamp()],
+            )
+            .map_err(|e| e.to_string())?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Writes `(run_id, result)` pairs in one transaction.
+    fn record_all(&mut self, batch: &[(i64, CheckResult)]) -> Result<(), String> {
+        let tx = self.conn.transaction().map_err(|e| e.to_string())?;
+        {
+            let mut stmt = tx
+                .prepare_cached(
+                    "INSERT INTO checks (username, status, checked_at, run_id) VALUES (?1, ?2, ?3, ?4)",
+                )
+                .map_err(|e| e.to_string())?;
+            for (run_id, result) in batch {
+                stmt.execute(params![
+                    result.username,
+                    result.status.label(),
+                    result.checked_at.timestamp(),
+                    run_id
+                ])
+                .map_err(|e| e.to_string())?;
+            }
+        }
+        tx.commit().map_err(|e| e.to_string())
+    }
+
+    pub fn last_known(&self, username: &str) -> Result<Option<LastKnown>, String> {
+        self.last_known_before(username, i64::MAX)
+    }
+
+    /// The latest answer recorded no later than the `before` timestamp.
+    fn last_known_before(&self, username: &str, before: i64) -> Result<Option<LastKnown>, String> {
+        self.conn
+            .prepare_cached(
+                "SELECT status, checked_at FROM checks
+                 WHERE username = ?1 AND status IN ('available', 'taken') AND checked_at <= ?2
+                 ORDER BY checked_at DESC, id DESC LIMIT 1",
+            )
+            .and_then(|mut stmt| {
+                stmt.query_row(params![username, before], |row| {
+                    let status: String = row.get(0)?;
+                    let checked_at: i64 = row.get(1)?;
+                    Ok((status, checked_at))
+                })
+                .optional()
+            })
+            .map(|row| {
+                row.map(|(status, checked_at)| LastKnown {
+                    status: if status == "available" {
+                        ResultStatus::Available
+                    } else {
+                        ResultStatus::Taken
+                    },
+                    checked_at: DateTime::from_timestamp(checked_at, 0).unwrap_or_default(),
+                })
+            })
+            .map_err(|e| e.to_string())
+    }
+
+    /// Looks up every username that was answered by `before` (to the second),
+    /// keyed by the name as given. The lookups share one read transaction.
+    pub fn last_known_all(
+        &self,
+        usernames: impl IntoIterator<Item = String>,
+        before: DateTime<Utc>,
+    ) -> Result<HashMap<String, LastKnown>, String> {
+        let tx = self
+            .conn
+            .unchecked_transaction()
+            .map_err(|e| e.to_string())?;
+        let mut known = HashMap::new();
+        for username in usernames {
+            if let Some(last) = self.last_known_before(&username, before.timestamp())? {
+                known.insert(username, last);
+            }
+        }
+        tx.finish().map_err(|e| e.to_string())?;
+        Ok(known)
+    }
+}
+
+/// Writes results to history on a thread and connection of its own, so a
+/// long run doesn't wait on the database for every result. Whatever has
+/// queued up since the last write goes in one transaction.
+pub struct Recorder {
+    sender: Option<mpsc::Sender<(i64, CheckResult)>>,
+    thread: Option<JoinHandle<()>>,
+    /// Set when a write fails; the thread stops and later results are refused.
+    error: Arc<Mutex<Option<String>>>,
+}
+
+impl Recorder {
+    pub fn start(mut history: History) -> Result<Self, String> {
+        let (sender, receiver) = mpsc::channel::<(i64, CheckResult)>();
+        let error = Arc::new(Mutex::new(None));
+        let thread_error = Arc::clone(&error);
+        let thread = std::thread::Builder::new()
+            .name("history".to_string())
+            .spawn(move || {
+                while let Ok(first) = receiver.recv() {
+                    let mut batch = vec![first];
+                    batch.extend(receiver.try_iter().take(RECORD_BATCH_LIMIT));
+                    if let Err(e) = history.record_all(&batch) {
+                        *thread_error.lock().unwrap_or_else(|e| e.into_inner()) = Some(e);
+                        return;
+                    }
+                }
+            })
+            .map_err(|e| e.to_string())?;
+
+        Ok(Self {
+            sender: Some(sender),
+            thread: Some(thread),
+            error,
+        })
+    }
+
+    /// Queues a result; fails once an earlier write has failed.
+    pub fn record(&self, run_id: i64, result: &CheckResult) -> Result<(), String> {
+        let sent = self
+            .sender
+            .as_ref()
+            .is_some_and(|sender| sender.send((run_id, result.clone())).is_ok());
+        if sent {
+            Ok(())
+        } else {
+            Err(self.error())
+        }
+    }
+
+    /// Waits for everything queued to be written.
+    pub fn finish(mut self) -> Result<(), String> {
+        self.stop();
+        match self.error.lock().unwrap_or_else(|e| e.into_inner()).take() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    fn stop(&mut self) {
+        self.sender = None;
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+
+    fn error(&self) -> String {
+        self.error
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+            .unwrap_or_else(|| "History writer stopped".to_string())
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// [`History::last_known_all`] on a connection of its own, so a long list
+/// doesn't hold up the caller.
+pub async fn lookup(
+    usernames: impl IntoIterator<Item = String> + Send + 'static,
+    before: DateTime<Utc>,
+) -> Result<HashMap<String, LastKnown>, String> {
+    tokio::task::spawn_blocking(move || History::open()?.last_known_all(usernames, before))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Whether a username was last seen Taken within `window` and can be skipped.
+pub fn is_fresh_taken(last: Option<&LastKnown>, window: Duration) -> bool {
+    last.is_some_and(|last| {
+        last.status == ResultStatus::Taken && Utc::now() - last.checked_at < window
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(username: &str, status: ResultStatus) -> CheckResult {
+        CheckResult {
+            username: username.to_string(),
+            status,
+            attempts: 1,
+            latency: std::time::Duration::ZERO,
+            proxy: None,
+            checked_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn recorder_writes_everything_queued_before_finishing() {
+        let dir =
+            std::env::temp_dir().join(format!("hytale-checker-history-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(HISTORY_FILE);
+
+        let history = History::open_at(&path).unwrap();
+        let run_id = history.start_run(Source::Cli).unwrap();
+        let recorder = Recorder::start(History::open_at(&path).unwrap()).unwrap();
+        for i in 0..2_500 {
+            let status = if i % 2 == 0 {
+                ResultStatus::Available
+            } else {
+                ResultStatus::Taken
+            };
+            recorder
+                .record(run_id, &result(&format!("name{}", i), status))
+                .unwrap();
+        }
+        recorder.finish().unwrap();
+
+        let count: i64 = history
+            .conn
+            .query_row("SELECT COUNT(*) FROM checks", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 2_500);
+        let last = history.last_known("NAME2499").unwrap().unwrap();
+        assert_eq!(last.status, ResultStatus::Taken);
+        drop(history);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
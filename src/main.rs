@@ -1,17 +1,51 @@
 mod app;
-mod checker;
-mod proxy;
+mod autosave;
+mod checkpoint;
+mod cli;
+mod config;
+mod export;
+mod history;
+mod preflight;
+mod settings;
 mod ui;
+mod watchlist;
+mod wordlist;
 
+use clap::Parser;
 use iced::Theme;
+use std::process::ExitCode;
 
-fn main() -> iced::Result {
-    iced::application(app::App::new, app::App::update, app::App::view)
-        .title("Hytale Username Checker")
-        .theme(theme)
-        .window_size((960.0, 600.0))
-        .centered()
-        .run()
+fn main() -> ExitCode {
+    let args = cli::Cli::parse();
+    if let Some(command) = args.command {
+        return cli::run(command);
+    }
+
+    match run_gui() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run_gui() -> iced::Result {
+    let settings = settings::load();
+    let window = settings.as_ref().map(|s| s.window).unwrap_or_default();
+
+    iced::application(
+        move || app::App::new(settings.clone()),
+        app::App::update,
+        app::App::view,
+    )
+    .title("Hytale Username Checker")
+    .theme(theme)
+    .subscription(app::App::subscription)
+    .window_size((window.width, window.height))
+    .exit_on_close_request(false)
+    .centered()
+    .run()
 }
 
 fn theme(_state: &app::App) -> Theme {
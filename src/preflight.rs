@@ -0,0 +1,196 @@
+use hytale_checker_core::{InvalidReason, UsernameRules};
+use std::collections::HashSet;
+
+/// What normalizing, deduplicating and validating a username list leaves.
+/// Only counts are kept; [`usernames`] yields the names themselves.
+#[derive(Debug, Clone, Default)]
+pub struct Preflight {
+    /// Non-blank input lines.
+    pub lines: usize,
+    /// Lowercased, valid names, each counted once.
+    pub valid: usize,
+    /// Lines dropped because the same name (ignoring case) came earlier.
+    pub duplicates: usize,
+    pub rejected: Vec<Rejected>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Rejected {
+    /// 1-based line number in the input.
+    pub line: usize,
+    pub input: String,
+    pub reason: InvalidReason,
+}
+
+impl Preflight {
+    pub fn run<S: AsRef<str>>(lines: impl IntoIterator<Item = S>, rules: &UsernameRules) -> Self {
+        let mut preflight = Preflight::default();
+        let mut screen = Screen::new(rules.clone());
+
+        for (i, line) in lines.into_iter().enumerate() {
+            let outcome = screen.line(line.as_ref());
+            if !matches!(outcome, Screened::Blank) {
+                preflight.lines += 1;
+            }
+            match outcome {
+                Screened::Blank => {}
+                Screened::Valid(_) => preflight.valid += 1,
+                Screened::Duplicate => preflight.duplicates += 1,
+                Screened::Rejected(reason) => preflight.rejected.push(Rejected {
+                    line: i + 1,
+                    input: line.as_ref().trim().to_string(),
+                    reason,
+                }),
+            }
+        }
+        preflight
+    }
+
+    /// Whether anything was dropped, so the user should see the report
+    /// before the run starts.
+    pub fn needs_review(&self) -> bool {
+        self.duplicates > 0 || !self.rejected.is_empty()
+    }
+
+    /// e.g. "1,204 lines → 1,150 unique valid, 54 rejected".
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "{} lines → {} unique valid",
+            thousands(self.lines),
+            thousands(self.valid)
+        );
+        if self.duplicates > 0 {
+            summary.push_str(&format!(", {} duplicates", thousands(self.duplicates)));
+        }
+        if !self.rejected.is_empty() {
+            summary.push_str(&format!(", {} rejected", thousands(self.rejected.len())));
+        }
+        summary
+    }
+}
+
+/// The names [`Preflight::run`] counts as valid, in input order, as `lines`
+/// is read.
+pub fn usernames<S: AsRef<str>>(
+    lines: impl IntoIterator<Item = S>,
+    rules: UsernameRules,
+) -> impl Iterator<Item = String> {
+    let mut screen = Screen::new(rules);
+    lines
+        .into_iter()
+        .filter_map(move |line| match screen.line(line.as_ref()) {
+            Screened::Valid(username) => Some(username),
+            _ => None,
+        })
+}
+
+enum Screened {
+    Blank,
+    Valid(String),
+    Duplicate,
+    Rejected(InvalidReason),
+}
+
+/// Checks lines one at a time, remembering the names already seen.
+struct Screen {
+    rules: UsernameRules,
+    seen: HashSet<String>,
+}
+
+impl Screen {
+    fn new(rules: UsernameRules) -> Self {
+        Self {
+            rules,
+            seen: HashSet::new(),
+        }
+    }
+
+    fn line(&mut self, line: &str) -> Screened {
+        let input = line.trim();
+        if input.is_empty() {
+            return Screened::Blank;
+        }
+
+        let username = input.to_ascii_lowercase();
+        if let Err(reason) = self.rules.validate(&username) {
+            Screened::Rejected(reason)
+        } else if self.seen.insert(username.clone()) {
+            Screened::Valid(username)
+        } else {
+            Screened::Duplicate
+        }
+    }
+}
+
+fn thousands(n: usize) -> String {
+    let digits = n.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn folds_case_and_drops_duplicates() {
+        let lines = ["Alice", "  bob ", "", "ALICE", "alice", "Carol"];
+        let rules = UsernameRules::default();
+
+        let preflight = Preflight::run(lines, &rules);
+        assert_eq!(preflight.lines, 5);
+        assert_eq!(preflight.valid, 3);
+        assert_eq!(preflight.duplicates, 2);
+        assert!(preflight.rejected.is_empty());
+        assert!(preflight.needs_review());
+
+        let usernames: Vec<String> = usernames(lines, rules).collect();
+        assert_eq!(usernames, ["alice", "bob", "carol"]);
+    }
+
+    #[test]
+    fn rejects_lines_with_their_line_number_and_reason() {
+        let lines = ["good_name", "", "bad-name", "ab"];
+        let preflight = Preflight::run(lines, &UsernameRules::default());
+
+        assert_eq!(preflight.valid, 1);
+        let [bad_character, too_short] = &preflight.rejected[..] else {
+            panic!("expected two rejected lines: {:?}", preflight.rejected);
+        };
+        assert_eq!(bad_character.line, 3);
+        assert_eq!(bad_character.input, "bad-name");
+        assert_eq!(
+            bad_character.reason,
+            InvalidReason::BadCharacter {
+                character: '-',
+                position: 4
+            }
+        );
+        assert_eq!(
+            bad_character.reason.to_string(),
+            "bad character '-' at position 4"
+        );
+        assert_eq!(too_short.line, 4);
+        assert_eq!(preflight.summary(), "3 lines → 1 unique valid, 2 rejected");
+    }
+
+    #[test]
+    fn summary_groups_thousands() {
+        let preflight = Preflight {
+            lines: 1_204,
+            valid: 1_150,
+            duplicates: 0,
+            rejected: Vec::new(),
+        };
+        assert_eq!(preflight.summary(), "1,204 lines → 1,150 unique valid");
+        assert!(!preflight.needs_review());
+        assert_eq!(thousands(1_234_567), "1,234,567");
+        assert_eq!(thousands(999), "999");
+    }
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::app::Tab;
+use crate::checkpoint::RunSettings;
+use crate::config;
+
+const SETTINGS_FILE: &str = "settings.toml";
+
+/// Everything the window remembers between launches.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    #[serde(flatten)]
+    pub run: RunSettings,
+    pub last_tab: Tab,
+    pub show_proxy_panel: bool,
+    pub window: WindowSize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowSize {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Default for WindowSize {
+    fn default() -> Self {
+        Self {
+            width: 960.0,
+            height: 600.0,
+        }
+    }
+}
+
+fn settings_path() -> Option<PathBuf> {
+    config::config_dir().map(|dir| dir.join(SETTINGS_FILE))
+}
+
+/// Reads the saved settings, falling back to defaults when there are none yet.
+pub fn load() -> Result<Settings, String> {
+    config::load_or_default(settings_path(), |data| toml::from_slice(data))
+}
+
+/// Writes the settings synchronously so saves issued in quick succession
+/// land in order.
+pub fn save(settings: &Settings) -> Result<(), String> {
+    let path = settings_path().ok_or("No config directory available")?;
+    let data = toml::to_string_pretty(settings).map_err(|e| e.to_string())?;
+    config::write_atomic(&path, data.as_bytes())
+}
@@ -1,4 +1,6 @@
-use iced::widget::{button, container, row, space, text, text_editor, text_input};
+use iced::widget::{
+    button, center, container, mouse_area, opaque, row, space, stack, text, text_editor, text_input,
+};
 use iced::{border::Radius, Alignment, Background, Border, Color, Element, Fill};
 
 use super::theme;
@@ -113,6 +115,65 @@ pub fn secondary_button<'a>(label: &'a str, enabled: bool) -> button::Button<'a,
     })
 }
 
+pub fn toggle_chip<'a>(
+    label: impl text::IntoFragment<'a>,
+    active: bool,
+) -> button::Button<'a, Message> {
+    button(text(label).size(11).color(if active {
+        theme::TEXT_BRIGHT
+    } else {
+        theme::TEXT_MUTED
+    }))
+    .padding([7, 10])
+    .style(move |_, status| {
+        let bg = match status {
+            button::Status::Hovered | button::Status::Pressed if active => {
+                theme::ACCENT_CYAN.scale_alpha(0.3)
+            }
+            button::Status::Hovered | button::Status::Pressed => theme::BG_ELEVATED,
+            _ if active => theme::ACCENT_CYAN.scale_alpha(0.2),
+            _ => theme::BG_INPUT,
+        };
+        button::Style {
+            background: Some(Background::Color(bg)),
+            text_color: if active {
+                theme::TEXT_BRIGHT
+            } else {
+                theme::TEXT_MUTED
+            },
+            border: Border {
+                color: if active {
+                    theme::ACCENT_CYAN.scale_alpha(0.5)
+                } else {
+                    theme::BORDER_SUBTLE
+                },
+                width: 1.0,
+                radius: Radius::new(6),
+            },
+            ..Default::default()
+        }
+    })
+}
+
+/// Draws `content` centered over a dimmed `base`; clicking outside it sends `on_blur`.
+pub fn modal<'a>(
+    base: impl Into<Element<'a, Message>>,
+    content: impl Into<Element<'a, Message>>,
+    on_blur: Message,
+) -> Element<'a, Message> {
+    stack![
+        base.into(),
+        opaque(
+            mouse_area(center(opaque(content)).style(|_| container::Style {
+                background: Some(Background::Color(Color::BLACK.scale_alpha(0.6))),
+                ..Default::default()
+            }))
+            .on_press(on_blur)
+        )
+    ]
+    .into()
+}
+
 pub fn editor_style() -> text_editor::Style {
     text_editor::Style {
         background: Background::Color(theme::BG_INPUT),
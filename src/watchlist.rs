@@ -0,0 +1,155 @@
+use chrono::{DateTime, Duration, Utc};
+use hytale_checker_core::{CheckResult, ResultStatus};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::config;
+
+const WATCHLIST_FILE: &str = "watchlist.json";
+const DEFAULT_INTERVAL_MINUTES: u32 = 60;
+
+/// Usernames that are re-checked on a schedule until they're released.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Watchlist {
+    pub interval_minutes: u32,
+    pub entries: Vec<WatchEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchEntry {
+    pub username: String,
+    pub added_at: DateTime<Utc>,
+    /// The last definitive answer; errors leave it unchanged.
+    pub status: Option<ResultStatus>,
+    pub last_checked: Option<DateTime<Utc>>,
+    /// When the name was last seen going from Taken to Available.
+    pub released_at: Option<DateTime<Utc>>,
+}
+
+impl Default for Watchlist {
+    fn default() -> Self {
+        Self {
+            interval_minutes: DEFAULT_INTERVAL_MINUTES,
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl Watchlist {
+    /// Adds a username unless it's already watched; returns whether it was added.
+    pub fn add(&mut self, username: &str) -> bool {
+        if self.contains(username) {
+            return false;
+        }
+
+        self.entries.push(WatchEntry {
+            username: username.to_string(),
+            added_at: Utc::now(),
+            status: None,
+            last_checked: None,
+            released_at: None,
+        });
+        true
+    }
+
+    pub fn remove(&mut self, username: &str) {
+        self.entries
+            .retain(|e| !e.username.eq_ignore_ascii_case(username));
+    }
+
+    pub fn contains(&self, username: &str) -> bool {
+        self.entries
+            .iter()
+            .any(|e| e.username.eq_ignore_ascii_case(username))
+    }
+
+    /// Usernames whose last check is older than the interval.
+    pub fn due(&self, now: DateTime<Utc>) -> Vec<String> {
+        let interval = Duration::minutes(self.interval_minutes.max(1).into());
+        self.entries
+            .iter()
+            .filter(|e| e.last_checked.is_none_or(|at| now - at >= interval))
+            .map(|e| e.username.clone())
+            .collect()
+    }
+
+    /// Records a check and returns true when it shows the name was released.
+    pub fn apply(&mut self, result: &CheckResult) -> bool {
+        let Some(entry) = self
+            .entries
+            .iter_mut()
+            .find(|e| e.username == result.username)
+        else {
+            return false;
+        };
+
+        entry.last_checked = Some(result.checked_at);
+        if !result.status.is_definitive() || result.status == ResultStatus::Invalid {
+            return false;
+        }
+
+        let released =
+            entry.status == Some(ResultStatus::Taken) && result.status == ResultStatus::Available;
+        if released {
+            entry.released_at = Some(result.checked_at);
+        }
+        entry.status = Some(result.status.clone());
+        released
+    }
+}
+
+fn watchlist_path() -> Option<PathBuf> {
+    config::data_dir().map(|dir| dir.join(WATCHLIST_FILE))
+}
+
+pub fn load() -> Result<Watchlist, String> {
+    config::load_json(watchlist_path())
+}
+
+pub fn save(watchlist: &Watchlist) -> Result<(), String> {
+    let path = watchlist_path().ok_or("No data directory available")?;
+    let data = serde_json::to_vec_pretty(watchlist).map_err(|e| e.to_string())?;
+    config::write_atomic(&path, &data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(username: &str, status: ResultStatus) -> CheckResult {
+        CheckResult {
+            username: username.to_string(),
+            status,
+            attempts: 1,
+            latency: std::time::Duration::ZERO,
+            proxy: None,
+            checked_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn matches_names_ignoring_case() {
+        let mut watchlist = Watchlist::default();
+        assert!(watchlist.add("Notch"));
+        assert!(!watchlist.add("notch"));
+        assert!(watchlist.contains("NOTCH"));
+
+        watchlist.remove("nOtCh");
+        assert!(!watchlist.contains("notch"));
+        assert!(watchlist.entries.is_empty());
+    }
+
+    #[test]
+    fn reports_a_release_only_after_taken() {
+        let mut watchlist = Watchlist::default();
+        watchlist.add("notch");
+
+        assert!(!watchlist.apply(&result("notch", ResultStatus::Available)));
+        assert!(!watchlist.apply(&result("notch", ResultStatus::Taken)));
+        assert!(!watchlist.apply(&result("notch", ResultStatus::Unknown)));
+        assert!(watchlist.apply(&result("notch", ResultStatus::Available)));
+        assert!(watchlist.entries[0].released_at.is_some());
+        assert!(watchlist.due(Utc::now()).is_empty());
+    }
+}
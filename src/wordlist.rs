@@ -0,0 +1,147 @@
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Non-blank lines read from a file: seed words for mutations, or an
+/// imported proxy list.
+#[derive(Debug, Clone)]
+pub struct Wordlist {
+    pub name: String,
+    pub words: Arc<Vec<String>>,
+}
+
+/// Usernames too many for the editor. A file's lines aren't held in memory;
+/// they're read again each time the list is needed.
+#[derive(Debug, Clone)]
+pub struct UsernameList {
+    pub name: String,
+    pub file: Option<PathBuf>,
+    /// Names held in memory, which follow the file's.
+    pub words: Arc<Vec<String>>,
+    /// Non-blank lines across the file and `words`.
+    pub len: usize,
+}
+
+impl UsernameList {
+    pub fn from_words(name: impl Into<String>, words: Vec<String>) -> Self {
+        Self {
+            name: name.into(),
+            file: None,
+            len: words.iter().filter(|w| !w.trim().is_empty()).count(),
+            words: Arc::new(words),
+        }
+    }
+
+    /// Every line of the list, blank ones included. The file is only opened
+    /// here; it's read as the iterator advances.
+    pub fn lines(&self) -> Result<impl Iterator<Item = io::Result<String>> + Send + use<>, String> {
+        let file = match &self.file {
+            Some(path) => Some(read_lines(path).map_err(|e| format!("{}: {}", path.display(), e))?),
+            None => None,
+        };
+        let words = Arc::clone(&self.words);
+        Ok(file
+            .into_iter()
+            .flatten()
+            .chain((0..words.len()).map(move |i| Ok(words[i].clone()))))
+    }
+}
+
+pub async fn choose_file() -> Option<PathBuf> {
+    rfd::AsyncFileDialog::new()
+        .pick_file()
+        .await
+        .map(|handle| handle.path().to_path_buf())
+}
+
+pub async fn load(path: PathBuf) -> Result<Wordlist, String> {
+    let content = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+    let words = content
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    Ok(Wordlist {
+        name: file_name(&path),
+        words: Arc::new(words),
+    })
+}
+
+/// Reads a username file line by line. Files of up to `keep` names are held
+/// in memory; longer ones are only counted and left on disk.
+pub async fn load_usernames(path: PathBuf, keep: usize) -> Result<UsernameList, String> {
+    tokio::task::spawn_blocking(move || {
+        let error = |e: io::Error| format!("{}: {}", path.display(), e);
+        let mut words = Vec::new();
+        let mut len = 0;
+        for line in read_lines(&path).map_err(error)? {
+            let line = line.map_err(error)?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            len += 1;
+            if len <= keep {
+                words.push(line.to_string());
+            } else if len == keep + 1 {
+                words = Vec::new();
+            }
+        }
+
+        Ok(UsernameList {
+            name: file_name(&path),
+            file: (len > keep).then_some(path),
+            words: Arc::new(words),
+            len,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Lines as they're read, with bytes that aren't UTF-8 replaced rather than
+/// failing the whole file.
+fn read_lines(path: &Path) -> io::Result<impl Iterator<Item = io::Result<String>> + use<>> {
+    let reader = BufReader::new(std::fs::File::open(path)?);
+    Ok(reader
+        .split(b'\n')
+        .map(|line| line.map(|bytes| String::from_utf8_lossy(&bytes).into_owned())))
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn long_username_files_stay_on_disk() {
+        let dir =
+            std::env::temp_dir().join(format!("hytale-checker-wordlist-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("names.csv");
+        std::fs::write(&path, b"alpha\r\n\n  beta \ngam\xffma\n").unwrap();
+
+        let short = load_usernames(path.clone(), 3).await.unwrap();
+        assert_eq!(short.len, 3);
+        assert!(short.file.is_none());
+        assert_eq!(*short.words, ["alpha", "beta", "gam\u{fffd}ma"]);
+
+        let mut long = load_usernames(path.clone(), 2).await.unwrap();
+        assert_eq!(long.len, 3);
+        assert_eq!(long.file.as_deref(), Some(path.as_path()));
+        assert!(long.words.is_empty());
+
+        Arc::make_mut(&mut long.words).push("delta".to_string());
+        let lines: Vec<String> = long.lines().unwrap().map(Result::unwrap).collect();
+        assert_eq!(lines, ["alpha\r", "", "  beta ", "gam\u{fffd}ma", "delta"]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}